@@ -0,0 +1,159 @@
+#![no_main]
+
+use contract_for_fuzz_fuzz::budget::{validation_cost, Cost};
+use contract_for_fuzz_fuzz::fuzzcontract::{self, *};
+use contract_for_fuzz_fuzz::register_fuzz_contract;
+use contract_for_fuzz_fuzz::typed::TypedFuzzInstructionPrototype;
+use libfuzzer_sys::fuzz_target;
+use soroban_sdk::arbitrary::arbitrary::{self, Arbitrary, Unstructured};
+use soroban_sdk::arbitrary::fuzz_catch_panic;
+use soroban_sdk::testutils::Logs;
+use soroban_sdk::xdr::{ScErrorCode, ScErrorType};
+use soroban_sdk::{vec, Address, Bytes, Env, Error, IntoVal, Val, Vec};
+
+/// The fuzzed limits range up to this many times the validation cost. Much
+/// past that almost every instruction completes, and the host's
+/// out-of-budget paths go untested.
+const LIMIT_RANGE: u64 = 4;
+
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub struct TestCase {
+    cpu_limit: u32,
+    mem_limit: u32,
+    instruction: TypedFuzzInstructionPrototype,
+}
+
+fn print_logs_and_panic<T: core::fmt::Debug>(env: &Env, panic_r: T) -> ! {
+    if !env.logs().all().is_empty() {
+        env.logs().print();
+    }
+    panic!("host panicked: {panic_r:?}");
+}
+
+/// The number of objects the host holds, found from the handle it gives a
+/// new one. The host never frees objects, so the count only grows.
+fn object_count(env: &Env) -> u32 {
+    Bytes::new(env).to_object().get_handle()
+}
+
+/// The contract and the instruction for its `run`, set up the same way in
+/// every `Env` the input runs in, so they all hold the same host objects when
+/// the instruction runs.
+struct Call {
+    contract_id: Address,
+    instruction: FuzzInstruction,
+    /// What the host charges to invoke the contract at all.
+    validation: Cost,
+    /// What the host charges the client to convert the instruction into the
+    /// call's arguments, which it does before invoking the contract.
+    conversion: Cost,
+}
+
+impl Call {
+    fn new(env: &Env, input: &TestCase) -> Call {
+        let contract_id = register_fuzz_contract(env);
+
+        let client = fuzzcontract::Client::new(env, &contract_id);
+        let validation = validation_cost(env, &client);
+        let instruction = FuzzInstruction::Typed(input.instruction.to_guest(env));
+
+        let before = Cost::charged(env);
+        let _: Vec<Val> = vec![env, instruction.clone().into_val(env)];
+        let conversion = Cost::charged(env) - before;
+
+        Call {
+            contract_id,
+            instruction,
+            validation,
+            conversion,
+        }
+    }
+
+    /// Calls `run` through `try_run` under `limits`, or a default budget,
+    /// returning the objects the call left in the host and the error it
+    /// failed with, if any. Any host panic, including the budget running
+    /// out, is reported as a crash: `try_run` must return every failure as
+    /// an `Error`.
+    ///
+    /// The objects are counted with the budget unlimited, before the limits
+    /// are set and after the call, so counting them can neither run out of
+    /// budget nor be charged to the call.
+    fn try_run(&self, env: &Env, limits: Option<Cost>) -> (u32, Option<Error>) {
+        let client = fuzzcontract::Client::new(env, &self.contract_id);
+
+        env.budget().reset_unlimited();
+        let objects = object_count(env);
+        match limits {
+            Some(limits) => env.budget().reset_limits(limits.cpu, limits.mem),
+            None => env.budget().reset_default(),
+        }
+        let panic_r = fuzz_catch_panic(|| client.try_run(&self.instruction));
+        env.budget().reset_unlimited();
+        let objects = object_count(env) - objects;
+        env.budget().reset_default();
+
+        match panic_r {
+            Ok(Ok(_)) => (objects, None),
+            Ok(Err(Ok(e))) => (objects, Some(e)),
+            Ok(Err(Err(e))) => panic!("failed with an error that is not an `Error`: {e:?}"),
+            Err(e) => print_logs_and_panic(env, e),
+        }
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = TestCase::arbitrary_take_rest(Unstructured::new(data)) else {
+        return;
+    };
+
+    let env = Env::default();
+    let call = Call::new(&env, &input);
+    let ledger_entries = env.to_snapshot().ledger_entries;
+
+    // The limits leave room for the client's conversion of the instruction,
+    // so it is the contract invocation that runs out.
+    let range = Cost {
+        cpu: (call.validation.cpu * LIMIT_RANGE).max(1),
+        mem: (call.validation.mem * LIMIT_RANGE).max(1),
+    };
+    let limits = Cost {
+        cpu: call.conversion.cpu + u64::from(input.cpu_limit) % range.cpu,
+        mem: call.conversion.mem + u64::from(input.mem_limit) % range.mem,
+    };
+    let (objects, error) = call.try_run(&env, Some(limits));
+
+    let Some(e) = error else {
+        return;
+    };
+
+    // Without the limits, the instruction runs to completion or fails with
+    // its own error.
+    let reference_env = Env::default();
+    let (reference_objects, reference_error) =
+        Call::new(&reference_env, &input).try_run(&reference_env, None);
+
+    if e == Error::from_type_and_code(ScErrorType::Budget, ScErrorCode::ExceededLimit) {
+        assert_eq!(
+            env.to_snapshot().ledger_entries,
+            ledger_entries,
+            "running out of budget left the ledger changed"
+        );
+
+        // The run stopped partway through the reference run, so it cannot
+        // have made any object the reference run did not.
+        assert!(
+            objects <= reference_objects,
+            "running out of budget left {objects} objects, running to the end {reference_objects}"
+        );
+
+        // Nor may it leave the host unable to run the instruction again.
+        call.try_run(&env, None);
+    } else {
+        // Any other error is the instruction's own, so it must fail without
+        // the limits as well; if not, the limits surfaced as the wrong error.
+        assert!(
+            reference_error.is_some(),
+            "failed with {e:?} under limits, but succeeds without them"
+        );
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use contract_for_fuzz_fuzz::diff;
+use contract_for_fuzz_fuzz::FuzzInstructionPrototype;
+use libfuzzer_sys::fuzz_target;
+use soroban_sdk::arbitrary::arbitrary::{Arbitrary, Unstructured};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = FuzzInstructionPrototype::arbitrary_take_rest(Unstructured::new(data)) else {
+        return;
+    };
+
+    diff::check(&input, data.len());
+});
@@ -1,141 +1,220 @@
 #![no_main]
 
-use fuzzcontract::*;
+use contract_for_fuzz_fuzz::budget::{assert_same_charges, BudgetOracle, Cost};
+use contract_for_fuzz_fuzz::collections::{map::*, Mode};
+use contract_for_fuzz_fuzz::fuzzcontract::{self, *};
+use contract_for_fuzz_fuzz::register_fuzz_contract;
 use libfuzzer_sys::fuzz_target;
-use soroban_sdk::arbitrary::arbitrary;
+use soroban_sdk::arbitrary::arbitrary::{Arbitrary, Unstructured};
 use soroban_sdk::arbitrary::fuzz_catch_panic;
-use soroban_sdk::arbitrary::SorobanArbitrary;
 use soroban_sdk::testutils::Logs;
-use soroban_sdk::{Env, FromVal, Map, Val};
+use soroban_sdk::{Env, FromVal, Map, Val, Vec};
+use std::collections::BTreeMap;
 
-mod fuzzcontract {
-    soroban_sdk::contractimport!(
-        file = "../target/wasm32-unknown-unknown/release/contract_for_fuzz.wasm"
-    );
+/// The model's prediction for one operation.
+enum Step {
+    /// The host must return an error.
+    Fails,
+    /// The host must return this map.
+    Map(BTreeMap<u64, u64>),
+    /// The host returns something other than a map.
+    Other,
 }
 
-#[derive(Clone, Debug, arbitrary::Arbitrary)]
-pub struct TestCases {
-    map: <Map<u64, u64> as SorobanArbitrary>::Prototype,
-    tests: [TypedModMapPrototype; 10],
+/// Converts a host map returned by the contract into its entries, in host order.
+fn to_entries(env: &Env, v: &Val) -> std::vec::Vec<(u64, u64)> {
+    Map::<u64, u64>::from_val(env, v).iter().collect()
 }
 
-#[derive(Clone, Debug, arbitrary::Arbitrary)]
-pub enum TypedModMapPrototype {
-    MapDel(<Val as SorobanArbitrary>::Prototype),
-    MapGet(<Val as SorobanArbitrary>::Prototype),
-    MapHas(<Val as SorobanArbitrary>::Prototype),
-    MapKeyByPos(<u32 as SorobanArbitrary>::Prototype),
-    MapKeys,
-    MapLen,
-    MapNew,
-    MapNewFromLinearMemory(
-        <u32 as SorobanArbitrary>::Prototype,
-        <u32 as SorobanArbitrary>::Prototype,
-        <u32 as SorobanArbitrary>::Prototype,
-    ),
-    MapPut(
-        <Val as SorobanArbitrary>::Prototype,
-        <Val as SorobanArbitrary>::Prototype,
-    ),
-    MapUnpackToLinearMemory(
-        <u32 as SorobanArbitrary>::Prototype,
-        <u32 as SorobanArbitrary>::Prototype,
-        <u32 as SorobanArbitrary>::Prototype,
-    ),
-    MapValByPos(<u32 as SorobanArbitrary>::Prototype),
-    MapValues,
+fn to_vec(env: &Env, v: &Val) -> std::vec::Vec<u64> {
+    Vec::<u64>::from_val(env, v).iter().collect()
 }
 
-impl TypedModMapPrototype {
-    fn to_guest(&self, env: &Env, map: &Map<Val, Val>) -> TypedFuzzInstruction {
+/// The native model the host's results for an operation are checked against.
+trait Model {
+    /// Applies this operation to a `BTreeMap` model of `map`.
+    fn step(&self, model: &BTreeMap<u64, u64>) -> Step;
+
+    /// Checks the host's result for this operation against the model.
+    /// Comparing entry lists in host order also checks that the host keeps
+    /// maps sorted by key.
+    ///
+    /// `result` is `None` if the call returned an error.
+    fn check(&self, env: &Env, model: &BTreeMap<u64, u64>, result: Option<Val>);
+}
+
+impl Model for TypedModMapPrototype {
+    fn step(&self, model: &BTreeMap<u64, u64>) -> Step {
+        let len = model.len();
+        let mut model = model.clone();
         match self {
-            TypedModMapPrototype::MapDel(v) => {
-                let v = Val::from_val(env, v);
-                TypedFuzzInstruction::Map(TypedModMap::MapDel(
-                    map.clone(),
-                    FakeVal(v.get_payload()),
-                ))
+            TypedModMapPrototype::MapDel(k) | TypedModMapPrototype::MapGet(k)
+                if !model.contains_key(k) =>
+            {
+                return Step::Fails
             }
-            TypedModMapPrototype::MapGet(v) => {
-                let v = Val::from_val(env, v);
-                TypedFuzzInstruction::Map(TypedModMap::MapGet(
-                    map.clone(),
-                    FakeVal(v.get_payload()),
-                ))
+            TypedModMapPrototype::MapKeyByPos(i) | TypedModMapPrototype::MapValByPos(i)
+                if *i as usize >= len =>
+            {
+                return Step::Fails
             }
-            TypedModMapPrototype::MapHas(v) => {
-                let v = Val::from_val(env, v);
-                TypedFuzzInstruction::Map(TypedModMap::MapHas(
-                    map.clone(),
-                    FakeVal(v.get_payload()),
-                ))
+            TypedModMapPrototype::MapDel(k) => {
+                model.remove(k);
             }
-            TypedModMapPrototype::MapKeyByPos(v) => {
-                TypedFuzzInstruction::Map(TypedModMap::MapKeyByPos(map.clone(), *v))
+            TypedModMapPrototype::MapNew => model.clear(),
+            TypedModMapPrototype::MapPut(k, v) => {
+                model.insert(*k, *v);
             }
-            TypedModMapPrototype::MapKeys => {
-                TypedFuzzInstruction::Map(TypedModMap::MapKeys(map.clone()))
+            _ => return Step::Other,
+        }
+        Step::Map(model)
+    }
+
+    fn check(&self, env: &Env, model: &BTreeMap<u64, u64>, result: Option<Val>) {
+        match self {
+            TypedModMapPrototype::MapGet(k) => {
+                let expected = model.get(k).copied();
+                assert_eq!(result.map(|v| u64::from_val(env, &v)), expected);
             }
-            TypedModMapPrototype::MapLen => {
-                TypedFuzzInstruction::Map(TypedModMap::MapLen(map.clone()))
+            TypedModMapPrototype::MapHas(k) => {
+                let r = result.expect("map_has failed");
+                assert_eq!(bool::from_val(env, &r), model.contains_key(k));
             }
-            TypedModMapPrototype::MapNew => TypedFuzzInstruction::Map(TypedModMap::MapNew),
-            TypedModMapPrototype::MapNewFromLinearMemory(v_0, v_1, v_2) => {
-                TypedFuzzInstruction::Map(TypedModMap::MapNewFromLinearMemory(*v_0, *v_1, *v_2))
+            TypedModMapPrototype::MapKeyByPos(i) => {
+                let expected = model.keys().nth(*i as usize).copied();
+                assert_eq!(result.map(|v| u64::from_val(env, &v)), expected);
             }
-            TypedModMapPrototype::MapPut(v_0, v_1) => {
-                let v_0 = Val::from_val(env, v_0);
-                let v_1 = Val::from_val(env, v_1);
-                TypedFuzzInstruction::Map(TypedModMap::MapPut(
-                    map.clone(),
-                    FakeVal(v_0.get_payload()),
-                    FakeVal(v_1.get_payload()),
-                ))
+            TypedModMapPrototype::MapKeys => {
+                let r = result.expect("map_keys failed");
+                let expected: std::vec::Vec<u64> = model.keys().copied().collect();
+                assert_eq!(to_vec(env, &r), expected);
             }
-            TypedModMapPrototype::MapUnpackToLinearMemory(v_0, v_1, v_2) => {
-                TypedFuzzInstruction::Map(TypedModMap::MapUnpackToLinearMemory(
-                    map.clone(),
-                    *v_0,
-                    *v_1,
-                    *v_2,
-                ))
+            TypedModMapPrototype::MapLen => {
+                let r = result.expect("map_len failed");
+                assert_eq!(u32::from_val(env, &r) as usize, model.len());
             }
-            TypedModMapPrototype::MapValByPos(v) => {
-                TypedFuzzInstruction::Map(TypedModMap::MapValByPos(map.clone(), *v))
+            TypedModMapPrototype::MapNewFromLinearMemory(..)
+            | TypedModMapPrototype::MapUnpackToLinearMemory(..) => {}
+            TypedModMapPrototype::MapValByPos(i) => {
+                let expected = model.values().nth(*i as usize).copied();
+                assert_eq!(result.map(|v| u64::from_val(env, &v)), expected);
             }
             TypedModMapPrototype::MapValues => {
-                TypedFuzzInstruction::Map(TypedModMap::MapValues(map.clone()))
+                let r = result.expect("map_values failed");
+                let expected: std::vec::Vec<u64> = model.values().copied().collect();
+                assert_eq!(to_vec(env, &r), expected);
+            }
+            // Every other operation returns a new map.
+            _ => {
+                let expected = match self.step(model) {
+                    Step::Map(model) => Some(model.into_iter().collect()),
+                    _ => None,
+                };
+                assert_eq!(result.map(|v| to_entries(env, &v)), expected);
             }
         }
     }
 }
 
-fuzz_target!(|input: TestCases| {
+fn print_logs_and_panic<T: core::fmt::Debug>(env: &Env, panic_r: T) -> ! {
+    if !env.logs().all().is_empty() {
+        env.logs().print();
+    }
+    panic!("host panicked: {panic_r:?}");
+}
+
+/// Runs the input's operations and returns what each call charged.
+fn run(input: &TestCases, input_len: usize) -> std::vec::Vec<Cost> {
     let env = Env::default();
     env.budget().reset_unlimited();
 
-    let contract_id = env.register_contract_wasm(None, fuzzcontract::WASM);
+    let contract_id = register_fuzz_contract(&env);
 
     let client = fuzzcontract::Client::new(&env, &contract_id);
+    let mut budget = BudgetOracle::new(&env, &client);
 
     let map = Map::<u64, u64>::from_val(&env, &input.map);
-    let map = Map::<Val, Val>::from_val(&env, map.as_val());
+    let mut model: BTreeMap<u64, u64> = map.iter().collect();
+    let mut map = Map::<Val, Val>::from_val(&env, map.as_val());
 
-    for test in input.tests {
-        let fuzz_instruction = test.to_guest(&env, &map);
+    if let Mode::Contract = input.mode {
+        // A failing instruction aborts the whole program, so only build it
+        // up to the first operation the model expects to fail.
+        let mut instructions = Vec::new(&env);
+        let mut steps = std::vec::Vec::new();
+        let mut expect_ok = true;
+        let mut map_operand = MapOperand::Val(map.clone());
+        for test in input.tests.iter().filter(|t| !t.uses_linear_memory()) {
+            let instr = test.to_guest(&env, &map_operand);
+            instructions.push_back(FuzzInstruction::Typed(instr));
+            steps.push((test, model.clone()));
+            match test.step(&model) {
+                Step::Fails => {
+                    expect_ok = false;
+                    break;
+                }
+                Step::Map(next) => {
+                    map_operand = MapOperand::Reg(instructions.len() - 1);
+                    model = next;
+                }
+                Step::Other => {}
+            }
+        }
+        let program = FuzzProgram { instructions };
+
+        // Returning an error is ok; panicking is not.
+        let panic_r = budget.call(&env, || {
+            fuzz_catch_panic(|| client.try_run_program(&program))
+        });
+
+        let results = match panic_r {
+            Ok(Ok(Ok(results))) => Some(results),
+            Ok(_) => None,
+            Err(e) => print_logs_and_panic(&env, e),
+        };
+        assert_eq!(results.is_some(), expect_ok);
+        if let Some(results) = results {
+            for (i, (test, model)) in steps.iter().enumerate() {
+                let result = results.get_unchecked(i as u32);
+                test.check(&env, model, Some(result));
+            }
+        }
+        budget.call_each(&env, &client, &program);
+        return budget.finish(input_len);
+    }
+
+    for test in input.tests.iter() {
+        let fuzz_instruction = test.to_guest(&env, &MapOperand::Val(map.clone()));
         let fuzz_instruction = FuzzInstruction::Typed(fuzz_instruction);
 
         // Returning an error is ok; panicking is not.
-        let panic_r = fuzz_catch_panic(|| {
-            let _call_r = client.try_run(&fuzz_instruction);
+        let panic_r = budget.call(&env, || {
+            fuzz_catch_panic(|| client.try_run(&fuzz_instruction))
         });
 
-        if panic_r.is_err() {
-            if !env.logs().all().is_empty() {
-                env.logs().print();
+        let result = match panic_r {
+            Ok(Ok(Ok(v))) => Some(v),
+            Ok(_) => None,
+            Err(e) => print_logs_and_panic(&env, e),
+        };
+        test.check(&env, &model, result);
+
+        if let Mode::Harness = input.mode {
+            if let (Step::Map(next), Some(v)) = (test.step(&model), result) {
+                map = Map::<Val, Val>::from_val(&env, &v);
+                model = next;
             }
-            panic!("host panicked: {panic_r:?}");
         }
     }
+
+    budget.finish(input_len)
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = TestCases::arbitrary_take_rest(Unstructured::new(data)) else {
+        return;
+    };
+
+    let charges = run(&input, data.len());
+    assert_same_charges(&charges, &run(&input, data.len()));
 });
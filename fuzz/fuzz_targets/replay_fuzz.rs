@@ -0,0 +1,93 @@
+#![no_main]
+
+use contract_for_fuzz_fuzz::budget::Cost;
+use contract_for_fuzz_fuzz::fuzzcontract::{self, *};
+use contract_for_fuzz_fuzz::register_fuzz_contract;
+use contract_for_fuzz_fuzz::FuzzInstructionPrototype;
+use libfuzzer_sys::fuzz_target;
+use soroban_sdk::arbitrary::arbitrary::{self, Arbitrary, Unstructured};
+use soroban_sdk::arbitrary::fuzz_catch_panic;
+use soroban_sdk::testutils::{Events, Ledger, Logs};
+use soroban_sdk::xdr::ScVal;
+use soroban_sdk::{Env, TryFromVal, Vec};
+
+/// A program and everything about the `Env` it runs in that isn't fixed by
+/// `Env::default`.
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub struct TestCase {
+    prng_seed: [u8; 32],
+    /// How many ledgers pass between registering the contract and the call.
+    /// Kept small, so the contract has not yet expired.
+    ledger_advance: u8,
+    timestamp: u64,
+    network_id: [u8; 32],
+    instructions: std::vec::Vec<FuzzInstructionPrototype>,
+}
+
+/// Everything observable about running a program.
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    result: Result<ScVal, std::string::String>,
+    events: ScVal,
+    logs: std::vec::Vec<std::string::String>,
+    cost: Cost,
+    /// The ledger snapshot, serialized, so that every entry is compared byte
+    /// for byte.
+    ledger: std::vec::Vec<u8>,
+}
+
+/// Runs the program in a fresh `Env`, or returns `None` if the host
+/// panicked, either during the call or while its effects were being read
+/// back.
+fn run(input: &TestCase) -> Option<Outcome> {
+    let env = Env::default();
+    env.host().set_base_prng_seed(input.prng_seed).unwrap();
+
+    let contract_id = register_fuzz_contract(&env);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number += u32::from(input.ledger_advance);
+        li.timestamp = input.timestamp;
+        li.network_id = input.network_id;
+    });
+
+    let client = fuzzcontract::Client::new(&env, &contract_id);
+
+    let mut instructions = Vec::new(&env);
+    for instr in input.instructions.iter() {
+        instructions.push_back(instr.to_guest(&env));
+    }
+    let program = FuzzProgram { instructions };
+
+    let call_r = fuzz_catch_panic(|| client.try_run_program(&program)).ok()?;
+    let cost = Cost::charged(&env);
+
+    fuzz_catch_panic(|| {
+        let result = match call_r {
+            Ok(v) => Ok(ScVal::try_from_val(&env, &v.unwrap().to_val()).unwrap()),
+            Err(e) => Err(format!("{e:?}")),
+        };
+        let events = ScVal::try_from_val(&env, &env.events().all().to_val()).unwrap();
+        let mut ledger = std::vec::Vec::new();
+        env.to_snapshot().write(&mut ledger).unwrap();
+
+        Outcome {
+            result,
+            events,
+            logs: env.logs().all(),
+            cost,
+            ledger,
+        }
+    })
+    .ok()
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = TestCase::arbitrary_take_rest(Unstructured::new(data)) else {
+        return;
+    };
+
+    // Panics are reported by the other targets; here the two runs only have
+    // to agree, down to the last budget charge and ledger entry.
+    assert_eq!(run(&input), run(&input), "identical runs diverged");
+});
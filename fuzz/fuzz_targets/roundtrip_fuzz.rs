@@ -0,0 +1,62 @@
+#![no_main]
+
+use contract_for_fuzz_fuzz::budget::{assert_same_charges, BudgetOracle, Cost};
+use contract_for_fuzz_fuzz::fuzzcontract::{self, *};
+use contract_for_fuzz_fuzz::register_fuzz_contract;
+use contract_for_fuzz_fuzz::roundtrip::FuzzRoundTripPrototype;
+use libfuzzer_sys::fuzz_target;
+use soroban_sdk::arbitrary::arbitrary::{Arbitrary, Unstructured};
+use soroban_sdk::arbitrary::fuzz_catch_panic;
+use soroban_sdk::testutils::Logs;
+use soroban_sdk::{Env, FromVal};
+
+/// Runs each round trip in its own call, so one that fails, as most
+/// deserializations of arbitrary bytes do, does not stop the rest, and
+/// returns what each call charged.
+fn run(input: &[FuzzRoundTripPrototype], input_len: usize) -> std::vec::Vec<Cost> {
+    let env = Env::default();
+
+    let contract_id = register_fuzz_contract(&env);
+
+    let client = fuzzcontract::Client::new(&env, &contract_id);
+    let mut budget = BudgetOracle::new(&env, &client);
+
+    for round_trip in input {
+        let fuzz_instruction = FuzzInstruction::RoundTrip(round_trip.to_guest(&env));
+
+        // Returning an error is ok; panicking is not.
+        let panic_r = budget.call(&env, || {
+            fuzz_catch_panic(|| client.try_run(&fuzz_instruction))
+        });
+
+        match &panic_r {
+            Ok(Ok(Ok(cmp))) => {
+                assert_eq!(
+                    i64::from_val(&env, cmp),
+                    0,
+                    "{round_trip:?} did not come back to where it started"
+                );
+            }
+            Ok(_) => {}
+            Err(_) => {
+                if !env.logs().all().is_empty() {
+                    env.logs().print();
+                }
+                panic!("host panicked: {panic_r:?}");
+            }
+        }
+    }
+
+    budget.finish(input_len)
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) =
+        std::vec::Vec::<FuzzRoundTripPrototype>::arbitrary_take_rest(Unstructured::new(data))
+    else {
+        return;
+    };
+
+    let charges = run(&input, data.len());
+    assert_same_charges(&charges, &run(&input, data.len()));
+});
@@ -1,183 +1,246 @@
 #![no_main]
 
-use fuzzcontract::*;
+use contract_for_fuzz_fuzz::budget::{assert_same_charges, BudgetOracle, Cost};
+use contract_for_fuzz_fuzz::collections::{vec::*, Mode};
+use contract_for_fuzz_fuzz::fuzzcontract::{self, *};
+use contract_for_fuzz_fuzz::register_fuzz_contract;
 use libfuzzer_sys::fuzz_target;
-use soroban_sdk::arbitrary::arbitrary;
+use soroban_sdk::arbitrary::arbitrary::{Arbitrary, Unstructured};
 use soroban_sdk::arbitrary::fuzz_catch_panic;
-use soroban_sdk::arbitrary::SorobanArbitrary;
 use soroban_sdk::testutils::Logs;
 use soroban_sdk::{Env, FromVal, Val, Vec};
 
-mod fuzzcontract {
-    soroban_sdk::contractimport!(
-        file = "../target/wasm32-unknown-unknown/release/contract_for_fuzz.wasm"
-    );
+/// The model's prediction for one operation.
+enum Step {
+    /// The host must return an error.
+    Fails,
+    /// The host must return this vector.
+    Vec(std::vec::Vec<u64>),
+    /// The host returns something other than a vector.
+    Other,
 }
 
-#[derive(Clone, Debug, arbitrary::Arbitrary)]
-pub struct TestCases {
-    vec_0: <Vec<u64> as SorobanArbitrary>::Prototype,
-    vec_1: <Vec<u64> as SorobanArbitrary>::Prototype,
-    tests: [TypedModVecPrototype; 10],
+/// Converts a host vector returned by the contract into the native model type.
+fn to_model(env: &Env, v: &Val) -> std::vec::Vec<u64> {
+    Vec::<u64>::from_val(env, v).iter().collect()
 }
 
-#[derive(Clone, Debug, arbitrary::Arbitrary)]
-pub enum TypedModVecPrototype {
-    VecAppend,
-    VecBack,
-    VecBinarySearch(<u64 as SorobanArbitrary>::Prototype),
-    VecDel(<u32 as SorobanArbitrary>::Prototype),
-    VecFirstIndexOf(<u64 as SorobanArbitrary>::Prototype),
-    VecFront,
-    VecGet(<u32 as SorobanArbitrary>::Prototype),
-    VecInsert(
-        <u32 as SorobanArbitrary>::Prototype,
-        <u64 as SorobanArbitrary>::Prototype,
-    ),
-    VecLastIndexOf(<u64 as SorobanArbitrary>::Prototype),
-    VecLen,
-    VecNew,
-    VecNewFromLinearMemory(
-        <u32 as SorobanArbitrary>::Prototype,
-        <u32 as SorobanArbitrary>::Prototype,
-    ),
-    VecPopBack,
-    VecPopFront,
-    VecPushBack(<u64 as SorobanArbitrary>::Prototype),
-    VecPushFront(<u64 as SorobanArbitrary>::Prototype),
-    VecPut(
-        <u32 as SorobanArbitrary>::Prototype,
-        <u64 as SorobanArbitrary>::Prototype,
-    ),
-    VecSlice(
-        <u32 as SorobanArbitrary>::Prototype,
-        <u32 as SorobanArbitrary>::Prototype,
-    ),
-    VecUnpackToLinearMemory(
-        <u32 as SorobanArbitrary>::Prototype,
-        <u32 as SorobanArbitrary>::Prototype,
-    ),
+/// The native model the host's results for an operation are checked against.
+trait Model {
+    /// Applies this operation to a native `Vec<u64>` model of `v_0` and `v_1`.
+    fn step(&self, model_0: &[u64], model_1: &[u64]) -> Step;
+
+    /// Checks the host's result for this operation against the model.
+    ///
+    /// `result` is `None` if the call returned an error.
+    fn check(&self, env: &Env, model_0: &[u64], model_1: &[u64], result: Option<Val>);
 }
 
-impl TypedModVecPrototype {
-    fn to_guest(&self, env: &Env, v_0: &Vec<Val>, v_1: &Vec<Val>) -> TypedFuzzInstruction {
+impl Model for TypedModVecPrototype {
+    fn step(&self, model_0: &[u64], model_1: &[u64]) -> Step {
+        let len = model_0.len();
+        let mut model = model_0.to_vec();
         match self {
-            TypedModVecPrototype::VecAppend => {
-                TypedFuzzInstruction::Vec(TypedModVec::VecAppend(v_0.clone(), v_1.clone()))
+            TypedModVecPrototype::VecAppend => model.extend_from_slice(model_1),
+            TypedModVecPrototype::VecBack | TypedModVecPrototype::VecFront if len == 0 => {
+                return Step::Fails
             }
-            TypedModVecPrototype::VecBack => {
-                TypedFuzzInstruction::Vec(TypedModVec::VecBack(v_0.clone()))
+            TypedModVecPrototype::VecGet(i) if *i as usize >= len => return Step::Fails,
+            TypedModVecPrototype::VecDel(i) if (*i as usize) < len => {
+                model.remove(*i as usize);
             }
-            TypedModVecPrototype::VecBinarySearch(v) => {
-                let v = Val::from_val(env, v);
-                TypedFuzzInstruction::Vec(TypedModVec::VecBinarySearch(
-                    v_0.clone(),
-                    FakeVal(v.get_payload()),
-                ))
+            TypedModVecPrototype::VecInsert(i, x) if *i as usize <= len => {
+                model.insert(*i as usize, *x);
             }
-            TypedModVecPrototype::VecDel(v) => {
-                TypedFuzzInstruction::Vec(TypedModVec::VecDel(v_0.clone(), *v))
+            TypedModVecPrototype::VecNew => model.clear(),
+            TypedModVecPrototype::VecPopBack | TypedModVecPrototype::VecPopFront if len == 0 => {
+                return Step::Fails
+            }
+            TypedModVecPrototype::VecPopBack => {
+                model.pop();
             }
-            TypedModVecPrototype::VecFirstIndexOf(v) => {
-                let v = Val::from_val(env, v);
-                TypedFuzzInstruction::Vec(TypedModVec::VecFirstIndexOf(
-                    v_0.clone(),
-                    FakeVal(v.get_payload()),
-                ))
+            TypedModVecPrototype::VecPopFront => {
+                model.remove(0);
+            }
+            TypedModVecPrototype::VecPushBack(x) => model.push(*x),
+            TypedModVecPrototype::VecPushFront(x) => model.insert(0, *x),
+            TypedModVecPrototype::VecPut(i, x) if (*i as usize) < len => model[*i as usize] = *x,
+            TypedModVecPrototype::VecSlice(start, end) => {
+                match model_0.get(*start as usize..*end as usize) {
+                    Some(s) => model = s.to_vec(),
+                    None => return Step::Fails,
+                }
+            }
+            TypedModVecPrototype::VecDel(_)
+            | TypedModVecPrototype::VecInsert(..)
+            | TypedModVecPrototype::VecPut(..) => return Step::Fails,
+            _ => return Step::Other,
+        }
+        Step::Vec(model)
+    }
+
+    fn check(&self, env: &Env, model_0: &[u64], model_1: &[u64], result: Option<Val>) {
+        let len = model_0.len();
+        match self {
+            TypedModVecPrototype::VecBack => {
+                let expected = model_0.last().copied();
+                assert_eq!(result.map(|v| u64::from_val(env, &v)), expected);
+            }
+            TypedModVecPrototype::VecBinarySearch(x) => {
+                // The host's answer is only specified for sorted input.
+                if !model_0.windows(2).all(|w| w[0] <= w[1]) {
+                    return;
+                }
+                let r = u64::from_val(env, &result.expect("vec_binary_search failed"));
+                let (found, index) = (r >> 32, (r & 0xffff_ffff) as usize);
+                if model_0.contains(x) {
+                    assert_eq!(found, 1);
+                    assert_eq!(model_0.get(index), Some(x));
+                } else {
+                    assert_eq!(found, 0);
+                    assert_eq!(index, model_0.partition_point(|e| e < x));
+                }
+            }
+            TypedModVecPrototype::VecFirstIndexOf(x) => {
+                let expected = model_0.iter().position(|e| e == x).map(|i| i as u32);
+                let r = result.expect("vec_first_index_of failed");
+                assert_eq!(Option::<u32>::from_val(env, &r), expected);
             }
             TypedModVecPrototype::VecFront => {
-                TypedFuzzInstruction::Vec(TypedModVec::VecFront(v_0.clone()))
-            }
-            TypedModVecPrototype::VecGet(v) => {
-                TypedFuzzInstruction::Vec(TypedModVec::VecGet(v_0.clone(), *v))
-            }
-            TypedModVecPrototype::VecInsert(v_1, v_2) => {
-                let v_2 = Val::from_val(env, v_2);
-                TypedFuzzInstruction::Vec(TypedModVec::VecInsert(
-                    v_0.clone(),
-                    *v_1,
-                    FakeVal(v_2.get_payload()),
-                ))
-            }
-            TypedModVecPrototype::VecLastIndexOf(v) => {
-                let v_1 = Val::from_val(env, v);
-                TypedFuzzInstruction::Vec(TypedModVec::VecLastIndexOf(
-                    v_0.clone(),
-                    FakeVal(v_1.get_payload()),
-                ))
+                let expected = model_0.first().copied();
+                assert_eq!(result.map(|v| u64::from_val(env, &v)), expected);
             }
-            TypedModVecPrototype::VecLen => {
-                TypedFuzzInstruction::Vec(TypedModVec::VecLen(v_0.clone()))
+            TypedModVecPrototype::VecGet(i) => {
+                let expected = model_0.get(*i as usize).copied();
+                assert_eq!(result.map(|v| u64::from_val(env, &v)), expected);
             }
-            TypedModVecPrototype::VecNew => TypedFuzzInstruction::Vec(TypedModVec::VecNew),
-            TypedModVecPrototype::VecNewFromLinearMemory(v_0, v_1) => {
-                TypedFuzzInstruction::Vec(TypedModVec::VecNewFromLinearMemory(*v_0, *v_1))
+            TypedModVecPrototype::VecLastIndexOf(x) => {
+                let expected = model_0.iter().rposition(|e| e == x).map(|i| i as u32);
+                let r = result.expect("vec_last_index_of failed");
+                assert_eq!(Option::<u32>::from_val(env, &r), expected);
             }
-            TypedModVecPrototype::VecPopBack => {
-                TypedFuzzInstruction::Vec(TypedModVec::VecPopBack(v_0.clone()))
+            TypedModVecPrototype::VecLen => {
+                let r = result.expect("vec_len failed");
+                assert_eq!(u32::from_val(env, &r) as usize, len);
+            }
+            TypedModVecPrototype::VecNewFromLinearMemory(..)
+            | TypedModVecPrototype::VecUnpackToLinearMemory(..) => {}
+            // Every other operation returns a new vector.
+            _ => {
+                let expected = match self.step(model_0, model_1) {
+                    Step::Vec(model) => Some(model),
+                    _ => None,
+                };
+                assert_eq!(result.map(|v| to_model(env, &v)), expected);
             }
-            TypedModVecPrototype::VecPopFront => {
-                TypedFuzzInstruction::Vec(TypedModVec::VecPopFront(v_0.clone()))
-            }
-            TypedModVecPrototype::VecPushBack(v) => {
-                let v_1 = Val::from_val(env, v);
-                TypedFuzzInstruction::Vec(TypedModVec::VecPushBack(
-                    v_0.clone(),
-                    FakeVal(v_1.get_payload()),
-                ))
-            }
-            TypedModVecPrototype::VecPushFront(v) => {
-                let v_1 = Val::from_val(env, v);
-                TypedFuzzInstruction::Vec(TypedModVec::VecPushFront(
-                    v_0.clone(),
-                    FakeVal(v_1.get_payload()),
-                ))
-            }
-            TypedModVecPrototype::VecPut(v_1, v_2) => {
-                let v_2 = Val::from_val(env, v_2);
-                TypedFuzzInstruction::Vec(TypedModVec::VecPut(
-                    v_0.clone(),
-                    *v_1,
-                    FakeVal(v_2.get_payload()),
-                ))
-            }
-            TypedModVecPrototype::VecSlice(v_1, v_2) => {
-                TypedFuzzInstruction::Vec(TypedModVec::VecSlice(v_0.clone(), *v_1, *v_2))
-            }
-            TypedModVecPrototype::VecUnpackToLinearMemory(v_1, v_2) => TypedFuzzInstruction::Vec(
-                TypedModVec::VecUnpackToLinearMemory(v_0.clone(), *v_1, *v_2),
-            ),
         }
     }
 }
 
-fuzz_target!(|input: TestCases| {
+fn print_logs_and_panic<T: core::fmt::Debug>(env: &Env, panic_r: T) -> ! {
+    if !env.logs().all().is_empty() {
+        env.logs().print();
+    }
+    panic!("host panicked: {panic_r:?}");
+}
+
+/// Runs the input's operations and returns what each call charged.
+fn run(input: &TestCases, input_len: usize) -> std::vec::Vec<Cost> {
     let env = Env::default();
     env.budget().reset_unlimited();
 
-    let contract_id = env.register_contract_wasm(None, fuzzcontract::WASM);
+    let contract_id = register_fuzz_contract(&env);
 
     let client = fuzzcontract::Client::new(&env, &contract_id);
+    let mut budget = BudgetOracle::new(&env, &client);
 
     let v_0 = Vec::<u64>::from_val(&env, &input.vec_0);
     let v_1 = Vec::<u64>::from_val(&env, &input.vec_1);
-    let v_0 = Vec::<Val>::from_val(&env, &v_0);
+    let mut model_0: std::vec::Vec<u64> = v_0.iter().collect();
+    let model_1: std::vec::Vec<u64> = v_1.iter().collect();
+    let mut v_0 = Vec::<Val>::from_val(&env, &v_0);
     let v_1 = Vec::<Val>::from_val(&env, &v_1);
 
-    for test in input.tests {
-        let fuzz_instruction = test.to_guest(&env, &v_0, &v_1);
+    if let Mode::Contract = input.mode {
+        // A failing instruction aborts the whole program, so only build it
+        // up to the first operation the model expects to fail.
+        let mut instructions = Vec::new(&env);
+        let mut steps = std::vec::Vec::new();
+        let mut expect_ok = true;
+        let mut vec_operand = VecOperand::Val(v_0.clone());
+        let v_1_operand = VecOperand::Val(v_1.clone());
+        for test in input.tests.iter().filter(|t| !t.uses_linear_memory()) {
+            let instr = test.to_guest(&env, &vec_operand, &v_1_operand);
+            instructions.push_back(FuzzInstruction::Typed(instr));
+            steps.push((test, model_0.clone()));
+            match test.step(&model_0, &model_1) {
+                Step::Fails => {
+                    expect_ok = false;
+                    break;
+                }
+                Step::Vec(model) => {
+                    vec_operand = VecOperand::Reg(instructions.len() - 1);
+                    model_0 = model;
+                }
+                Step::Other => {}
+            }
+        }
+        let program = FuzzProgram { instructions };
+
+        // Returning an error is ok; panicking is not.
+        let panic_r = budget.call(&env, || {
+            fuzz_catch_panic(|| client.try_run_program(&program))
+        });
+
+        let results = match panic_r {
+            Ok(Ok(Ok(results))) => Some(results),
+            Ok(_) => None,
+            Err(e) => print_logs_and_panic(&env, e),
+        };
+        assert_eq!(results.is_some(), expect_ok);
+        if let Some(results) = results {
+            for (i, (test, model)) in steps.iter().enumerate() {
+                let result = results.get_unchecked(i as u32);
+                test.check(&env, model, &model_1, Some(result));
+            }
+        }
+        budget.call_each(&env, &client, &program);
+        return budget.finish(input_len);
+    }
+
+    for test in input.tests.iter() {
+        let operands = (VecOperand::Val(v_0.clone()), VecOperand::Val(v_1.clone()));
+        let fuzz_instruction = test.to_guest(&env, &operands.0, &operands.1);
         let fuzz_instruction = FuzzInstruction::Typed(fuzz_instruction);
 
         // Returning an error is ok; panicking is not.
-        let panic_r = fuzz_catch_panic(|| {
-            let _call_r = client.try_run(&fuzz_instruction);
+        let panic_r = budget.call(&env, || {
+            fuzz_catch_panic(|| client.try_run(&fuzz_instruction))
         });
 
-        if panic_r.is_err() {
-            if !env.logs().all().is_empty() {
-                env.logs().print();
+        let result = match panic_r {
+            Ok(Ok(Ok(v))) => Some(v),
+            Ok(_) => None,
+            Err(e) => print_logs_and_panic(&env, e),
+        };
+        test.check(&env, &model_0, &model_1, result);
+
+        if let Mode::Harness = input.mode {
+            if let (Step::Vec(model), Some(v)) = (test.step(&model_0, &model_1), result) {
+                v_0 = Vec::<Val>::from_val(&env, &v);
+                model_0 = model;
             }
-            panic!("host panicked: {panic_r:?}");
         }
     }
+
+    budget.finish(input_len)
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = TestCases::arbitrary_take_rest(Unstructured::new(data)) else {
+        return;
+    };
+
+    let charges = run(&input, data.len());
+    assert_same_charges(&charges, &run(&input, data.len()));
 });
@@ -0,0 +1,280 @@
+use crate::fuzzcontract::{Client, FuzzInstruction, FuzzProgram, FuzzRoundTrip};
+use crate::panic_message;
+use core::ops::Sub;
+use soroban_env_host::budget::Budget;
+use soroban_sdk::arbitrary::fuzz_catch_panic;
+use soroban_sdk::testutils::Logs;
+use soroban_sdk::xdr::{ScErrorCode, ScErrorType};
+use soroban_sdk::{Bytes, Env, Error, InvokeError, Vec};
+
+/// An input may have the host charge as much as a default budget's limits,
+/// beyond the validation cost of each call, once per this many bytes before
+/// it is flagged. An instruction with its operands takes some tens of bytes,
+/// so one that charges more than a few thousandths of the limits on its own
+/// is flagged, far below the point where the host would stop it.
+pub const INPUT_BYTES_PER_BUDGET: u64 = 10_000;
+
+/// CPU instructions and memory bytes charged to a budget.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Cost {
+    pub cpu: u64,
+    pub mem: u64,
+}
+
+impl Cost {
+    /// What `env`'s budget has been charged so far.
+    pub fn charged(env: &Env) -> Cost {
+        let budget = env.budget();
+        Cost {
+            cpu: budget.cpu_instruction_cost(),
+            mem: budget.memory_bytes_cost(),
+        }
+    }
+
+    fn covers(self, other: Cost) -> bool {
+        self.cpu >= other.cpu && self.mem >= other.mem
+    }
+
+    /// How much more this is than `other`, or zero where it is not.
+    fn excess_over(self, other: Cost) -> Cost {
+        Cost {
+            cpu: self.cpu.saturating_sub(other.cpu),
+            mem: self.mem.saturating_sub(other.mem),
+        }
+    }
+
+    /// The limits of a default budget.
+    fn default_limits() -> Cost {
+        let budget = Budget::default();
+        Cost {
+            cpu: budget.get_cpu_insns_remaining().unwrap(),
+            mem: budget.get_mem_bytes_remaining().unwrap(),
+        }
+    }
+}
+
+/// What was charged between two readings of a budget, the later first. A
+/// budget is only ever charged more, so the later reading must cover the
+/// earlier.
+impl Sub for Cost {
+    type Output = Cost;
+
+    fn sub(self, other: Cost) -> Cost {
+        assert!(
+            self.covers(other),
+            "budget charges decreased from {other:?} to {self:?}"
+        );
+        Cost {
+            cpu: self.cpu - other.cpu,
+            mem: self.mem - other.mem,
+        }
+    }
+}
+
+/// Whether a host error, as returned by a `try_` client method, is the
+/// budget running out.
+pub fn exceeded_budget(e: &Error) -> bool {
+    *e == Error::from_type_and_code(ScErrorType::Budget, ScErrorCode::ExceededLimit)
+}
+
+/// Measures the validation cost, what the host charges to invoke the
+/// contract at all: the lesser of what it charges for an empty program and
+/// for a call that fails at its first host function, since a call that
+/// fails never pays for its result.
+pub fn validation_cost(env: &Env, client: &Client) -> Cost {
+    let program = FuzzProgram {
+        instructions: Vec::new(env),
+    };
+    let before = Cost::charged(env);
+    client.run_program(&program);
+    let succeeding = Cost::charged(env) - before;
+
+    // Empty bytes do not deserialize.
+    let instruction = FuzzInstruction::RoundTrip(FuzzRoundTrip::Deserialize(Bytes::new(env)));
+    let before = Cost::charged(env);
+    let _ = client.try_run(&instruction);
+    let failing = Cost::charged(env) - before;
+
+    Cost {
+        cpu: succeeding.cpu.min(failing.cpu),
+        mem: succeeding.mem.min(failing.mem),
+    }
+}
+
+/// Records what each contract call a fuzz target makes charges to the
+/// budget, and checks the charges for signs of mis-metering.
+pub struct BudgetOracle {
+    validation: Cost,
+    charges: std::vec::Vec<Cost>,
+    /// What `call_each` charged, instruction by instruction.
+    rerun_charges: std::vec::Vec<Cost>,
+    exhausted: bool,
+}
+
+impl BudgetOracle {
+    pub fn new(env: &Env, client: &Client) -> BudgetOracle {
+        BudgetOracle {
+            validation: validation_cost(env, client),
+            charges: std::vec::Vec::new(),
+            rerun_charges: std::vec::Vec::new(),
+            exhausted: false,
+        }
+    }
+
+    /// Makes one contract call, through a `try_` client method wrapped in
+    /// `fuzz_catch_panic`, and checks what it charged. The charges must not
+    /// decrease across the call.
+    pub fn call<T>(
+        &mut self,
+        env: &Env,
+        call: impl FnOnce() -> std::thread::Result<Result<T, Result<Error, InvokeError>>>,
+    ) -> std::thread::Result<Result<T, Result<Error, InvokeError>>> {
+        let before = Cost::charged(env);
+        let call_r = call();
+        let charge = Cost::charged(env) - before;
+
+        if let Ok(Err(e)) = &call_r {
+            assert!(
+                charge.covers(self.validation),
+                "failed call charged {charge:?}, less than the validation cost {:?}: {e:?}",
+                self.validation
+            );
+            self.exhausted |= matches!(e, Ok(e) if exceeded_budget(e));
+        }
+        self.charges.push(charge);
+
+        call_r
+    }
+
+    /// Runs each instruction of a program again, on its own through
+    /// `try_run`, so that what each instruction charges is recorded and not
+    /// only what the whole program did. Operands naming registers read
+    /// `Void` there, as they do at the start of any program, so instructions
+    /// taking objects from registers mostly fail.
+    ///
+    /// These charges are checked as any call's are, and compared between
+    /// runs, but left out of the total `finish` checks against the input's
+    /// size: the program already charged for each instruction, and the
+    /// failing re-runs say nothing about what it did.
+    ///
+    /// Returning an error is ok; panicking is not.
+    pub fn call_each(&mut self, env: &Env, client: &Client, program: &FuzzProgram) {
+        let exhausted = self.exhausted;
+        for (i, instr) in program.instructions.iter().enumerate() {
+            let panic_r = self.call(env, || fuzz_catch_panic(|| client.try_run(&instr)));
+            self.rerun_charges.extend(self.charges.pop());
+            if let Err(e) = panic_r {
+                if !env.logs().all().is_empty() {
+                    env.logs().print();
+                }
+                panic!(
+                    "host panicked running instruction {i} on its own: {}",
+                    panic_message(&*e)
+                );
+            }
+        }
+        self.exhausted = exhausted;
+    }
+
+    /// Checks the calls' charges, apart from those of `call_each`, against
+    /// the size of the input that made them, and returns what each call
+    /// charged, for the target to compare with a second run of the same
+    /// input.
+    ///
+    /// Inputs that ran out of budget are not flagged, since the host did
+    /// stop them.
+    pub fn finish(self, input_len: usize) -> std::vec::Vec<Cost> {
+        if !self.exhausted {
+            self.check_excess(input_len);
+        }
+        self.charges.into_iter().chain(self.rerun_charges).collect()
+    }
+
+    fn check_excess(&self, input_len: usize) {
+        let excess = self.charges.iter().fold(Cost::default(), |sum, c| {
+            let excess = c.excess_over(self.validation);
+            Cost {
+                cpu: sum.cpu + excess.cpu,
+                mem: sum.mem + excess.mem,
+            }
+        });
+        let limits = Cost::default_limits();
+        let len = input_len.max(1) as u64;
+        let max = Cost {
+            cpu: limits.cpu * len / INPUT_BYTES_PER_BUDGET,
+            mem: limits.mem * len / INPUT_BYTES_PER_BUDGET,
+        };
+        assert!(
+            max.covers(excess),
+            "input of {input_len} bytes charged {excess:?} beyond validation"
+        );
+    }
+}
+
+/// Checks that two runs of the same input charged the same, call by call.
+pub fn assert_same_charges(first: &[Cost], second: &[Cost]) {
+    assert_eq!(
+        first.len(),
+        second.len(),
+        "identical inputs made different numbers of calls"
+    );
+    for (i, (a, b)) in first.iter().zip(second).enumerate() {
+        assert_eq!(a, b, "identical inputs charged differently for call {i}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oracle(validation: Cost, charges: &[Cost]) -> BudgetOracle {
+        BudgetOracle {
+            validation,
+            charges: charges.to_vec(),
+            rerun_charges: std::vec::Vec::new(),
+            exhausted: false,
+        }
+    }
+
+    const VALIDATION: Cost = Cost {
+        cpu: 1_000_000,
+        mem: 100_000,
+    };
+
+    #[test]
+    #[should_panic(expected = "beyond validation")]
+    fn an_instruction_charging_a_tenth_of_the_limits_is_flagged() {
+        let limits = Cost::default_limits();
+        let charge = Cost {
+            cpu: VALIDATION.cpu + limits.cpu / 10,
+            mem: VALIDATION.mem + limits.mem / 10,
+        };
+        oracle(VALIDATION, &[charge]).finish(50);
+    }
+
+    #[test]
+    fn charges_within_the_threshold_are_not_flagged() {
+        let limits = Cost::default_limits();
+        let charge = Cost {
+            cpu: VALIDATION.cpu + limits.cpu * 50 / INPUT_BYTES_PER_BUDGET,
+            mem: VALIDATION.mem + limits.mem * 50 / INPUT_BYTES_PER_BUDGET,
+        };
+        oracle(VALIDATION, &[charge, VALIDATION]).finish(50);
+    }
+
+    #[test]
+    fn instructions_run_on_their_own_are_left_out_of_the_threshold() {
+        let limits = Cost::default_limits();
+        let mut oracle = oracle(VALIDATION, &[VALIDATION]);
+        oracle.rerun_charges.push(limits);
+        assert_eq!(oracle.finish(50), [VALIDATION, limits]);
+    }
+
+    #[test]
+    fn inputs_that_ran_out_of_budget_are_not_flagged() {
+        let limits = Cost::default_limits();
+        let mut oracle = oracle(VALIDATION, &[limits]);
+        oracle.exhausted = true;
+        oracle.finish(50);
+    }
+}
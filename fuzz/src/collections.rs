@@ -0,0 +1,305 @@
+//! The inputs of `vec_fuzz` and `map_fuzz`, shared with the tools that
+//! write inputs for them.
+
+use soroban_sdk::arbitrary::arbitrary;
+
+/// How the operations in a test case relate to each other.
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum Mode {
+    /// Every operation is applied to the original collection.
+    Independent,
+    /// Each collection returned by an operation becomes the operand of the
+    /// next, threaded through the harness across separate contract calls.
+    Harness,
+    /// As `Harness`, but the whole sequence runs in one contract call,
+    /// threaded through the contract's registers.
+    Contract,
+}
+
+/// The input of `vec_fuzz`.
+pub mod vec {
+    use super::Mode;
+    use crate::fuzzcontract::*;
+    use crate::FuzzPointerPrototype;
+    use soroban_sdk::arbitrary::arbitrary;
+    use soroban_sdk::arbitrary::SorobanArbitrary;
+    use soroban_sdk::{Env, FromVal, Val, Vec};
+
+    #[derive(Clone, Debug, arbitrary::Arbitrary)]
+    pub struct TestCases {
+        pub vec_0: <Vec<u64> as SorobanArbitrary>::Prototype,
+        pub vec_1: <Vec<u64> as SorobanArbitrary>::Prototype,
+        pub tests: [TypedModVecPrototype; 10],
+        pub mode: Mode,
+    }
+
+    #[derive(Clone, Debug, arbitrary::Arbitrary)]
+    pub enum TypedModVecPrototype {
+        VecAppend,
+        VecBack,
+        VecBinarySearch(<u64 as SorobanArbitrary>::Prototype),
+        VecDel(<u32 as SorobanArbitrary>::Prototype),
+        VecFirstIndexOf(<u64 as SorobanArbitrary>::Prototype),
+        VecFront,
+        VecGet(<u32 as SorobanArbitrary>::Prototype),
+        VecInsert(
+            <u32 as SorobanArbitrary>::Prototype,
+            <u64 as SorobanArbitrary>::Prototype,
+        ),
+        VecLastIndexOf(<u64 as SorobanArbitrary>::Prototype),
+        VecLen,
+        VecNew,
+        VecNewFromLinearMemory(FuzzPointerPrototype, <u32 as SorobanArbitrary>::Prototype),
+        VecPopBack,
+        VecPopFront,
+        VecPushBack(<u64 as SorobanArbitrary>::Prototype),
+        VecPushFront(<u64 as SorobanArbitrary>::Prototype),
+        VecPut(
+            <u32 as SorobanArbitrary>::Prototype,
+            <u64 as SorobanArbitrary>::Prototype,
+        ),
+        VecSlice(
+            <u32 as SorobanArbitrary>::Prototype,
+            <u32 as SorobanArbitrary>::Prototype,
+        ),
+        VecUnpackToLinearMemory(FuzzPointerPrototype, <u32 as SorobanArbitrary>::Prototype),
+    }
+
+    impl TypedModVecPrototype {
+        pub fn to_guest(
+            &self,
+            env: &Env,
+            v_0: &VecOperand,
+            v_1: &VecOperand,
+        ) -> TypedFuzzInstruction {
+            match self {
+                TypedModVecPrototype::VecAppend => {
+                    TypedFuzzInstruction::Vec(TypedModVec::VecAppend(v_0.clone(), v_1.clone()))
+                }
+                TypedModVecPrototype::VecBack => {
+                    TypedFuzzInstruction::Vec(TypedModVec::VecBack(v_0.clone()))
+                }
+                TypedModVecPrototype::VecBinarySearch(v) => {
+                    let v = Val::from_val(env, v);
+                    TypedFuzzInstruction::Vec(TypedModVec::VecBinarySearch(
+                        v_0.clone(),
+                        FuzzOperand::Val(FakeVal(v.get_payload())),
+                    ))
+                }
+                TypedModVecPrototype::VecDel(v) => {
+                    TypedFuzzInstruction::Vec(TypedModVec::VecDel(v_0.clone(), *v))
+                }
+                TypedModVecPrototype::VecFirstIndexOf(v) => {
+                    let v = Val::from_val(env, v);
+                    TypedFuzzInstruction::Vec(TypedModVec::VecFirstIndexOf(
+                        v_0.clone(),
+                        FuzzOperand::Val(FakeVal(v.get_payload())),
+                    ))
+                }
+                TypedModVecPrototype::VecFront => {
+                    TypedFuzzInstruction::Vec(TypedModVec::VecFront(v_0.clone()))
+                }
+                TypedModVecPrototype::VecGet(v) => {
+                    TypedFuzzInstruction::Vec(TypedModVec::VecGet(v_0.clone(), *v))
+                }
+                TypedModVecPrototype::VecInsert(v_1, v_2) => {
+                    let v_2 = Val::from_val(env, v_2);
+                    TypedFuzzInstruction::Vec(TypedModVec::VecInsert(
+                        v_0.clone(),
+                        *v_1,
+                        FuzzOperand::Val(FakeVal(v_2.get_payload())),
+                    ))
+                }
+                TypedModVecPrototype::VecLastIndexOf(v) => {
+                    let v_1 = Val::from_val(env, v);
+                    TypedFuzzInstruction::Vec(TypedModVec::VecLastIndexOf(
+                        v_0.clone(),
+                        FuzzOperand::Val(FakeVal(v_1.get_payload())),
+                    ))
+                }
+                TypedModVecPrototype::VecLen => {
+                    TypedFuzzInstruction::Vec(TypedModVec::VecLen(v_0.clone()))
+                }
+                TypedModVecPrototype::VecNew => TypedFuzzInstruction::Vec(TypedModVec::VecNew),
+                TypedModVecPrototype::VecNewFromLinearMemory(v_0, v_1) => {
+                    TypedFuzzInstruction::Vec(TypedModVec::VecNewFromLinearMemory(
+                        v_0.to_guest(env),
+                        *v_1,
+                    ))
+                }
+                TypedModVecPrototype::VecPopBack => {
+                    TypedFuzzInstruction::Vec(TypedModVec::VecPopBack(v_0.clone()))
+                }
+                TypedModVecPrototype::VecPopFront => {
+                    TypedFuzzInstruction::Vec(TypedModVec::VecPopFront(v_0.clone()))
+                }
+                TypedModVecPrototype::VecPushBack(v) => {
+                    let v_1 = Val::from_val(env, v);
+                    TypedFuzzInstruction::Vec(TypedModVec::VecPushBack(
+                        v_0.clone(),
+                        FuzzOperand::Val(FakeVal(v_1.get_payload())),
+                    ))
+                }
+                TypedModVecPrototype::VecPushFront(v) => {
+                    let v_1 = Val::from_val(env, v);
+                    TypedFuzzInstruction::Vec(TypedModVec::VecPushFront(
+                        v_0.clone(),
+                        FuzzOperand::Val(FakeVal(v_1.get_payload())),
+                    ))
+                }
+                TypedModVecPrototype::VecPut(v_1, v_2) => {
+                    let v_2 = Val::from_val(env, v_2);
+                    TypedFuzzInstruction::Vec(TypedModVec::VecPut(
+                        v_0.clone(),
+                        *v_1,
+                        FuzzOperand::Val(FakeVal(v_2.get_payload())),
+                    ))
+                }
+                TypedModVecPrototype::VecSlice(v_1, v_2) => {
+                    TypedFuzzInstruction::Vec(TypedModVec::VecSlice(v_0.clone(), *v_1, *v_2))
+                }
+                TypedModVecPrototype::VecUnpackToLinearMemory(v_1, v_2) => {
+                    TypedFuzzInstruction::Vec(TypedModVec::VecUnpackToLinearMemory(
+                        v_0.clone(),
+                        v_1.to_guest(env),
+                        *v_2,
+                    ))
+                }
+            }
+        }
+    }
+
+    impl TypedModVecPrototype {
+        /// Whether the outcome depends on guest memory, which the model doesn't track.
+        pub fn uses_linear_memory(&self) -> bool {
+            matches!(
+                self,
+                TypedModVecPrototype::VecNewFromLinearMemory(..)
+                    | TypedModVecPrototype::VecUnpackToLinearMemory(..)
+            )
+        }
+    }
+}
+
+/// The input of `map_fuzz`.
+pub mod map {
+    use super::Mode;
+    use crate::fuzzcontract::*;
+    use crate::FuzzPointerPrototype;
+    use soroban_sdk::arbitrary::arbitrary;
+    use soroban_sdk::arbitrary::SorobanArbitrary;
+    use soroban_sdk::{Env, FromVal, Map, Val};
+
+    #[derive(Clone, Debug, arbitrary::Arbitrary)]
+    pub struct TestCases {
+        pub map: <Map<u64, u64> as SorobanArbitrary>::Prototype,
+        pub tests: [TypedModMapPrototype; 10],
+        pub mode: Mode,
+    }
+
+    #[derive(Clone, Debug, arbitrary::Arbitrary)]
+    pub enum TypedModMapPrototype {
+        MapDel(<u64 as SorobanArbitrary>::Prototype),
+        MapGet(<u64 as SorobanArbitrary>::Prototype),
+        MapHas(<u64 as SorobanArbitrary>::Prototype),
+        MapKeyByPos(<u32 as SorobanArbitrary>::Prototype),
+        MapKeys,
+        MapLen,
+        MapNew,
+        MapNewFromLinearMemory(
+            FuzzPointerPrototype,
+            FuzzPointerPrototype,
+            <u32 as SorobanArbitrary>::Prototype,
+        ),
+        MapPut(
+            <u64 as SorobanArbitrary>::Prototype,
+            <u64 as SorobanArbitrary>::Prototype,
+        ),
+        MapUnpackToLinearMemory(
+            FuzzPointerPrototype,
+            FuzzPointerPrototype,
+            <u32 as SorobanArbitrary>::Prototype,
+        ),
+        MapValByPos(<u32 as SorobanArbitrary>::Prototype),
+        MapValues,
+    }
+
+    impl TypedModMapPrototype {
+        pub fn to_guest(&self, env: &Env, map: &MapOperand) -> TypedFuzzInstruction {
+            match self {
+                TypedModMapPrototype::MapDel(v) => {
+                    let v = Val::from_val(env, v);
+                    TypedFuzzInstruction::Map(TypedModMap::MapDel(
+                        map.clone(),
+                        FuzzOperand::Val(FakeVal(v.get_payload())),
+                    ))
+                }
+                TypedModMapPrototype::MapGet(v) => {
+                    let v = Val::from_val(env, v);
+                    TypedFuzzInstruction::Map(TypedModMap::MapGet(
+                        map.clone(),
+                        FuzzOperand::Val(FakeVal(v.get_payload())),
+                    ))
+                }
+                TypedModMapPrototype::MapHas(v) => {
+                    let v = Val::from_val(env, v);
+                    TypedFuzzInstruction::Map(TypedModMap::MapHas(
+                        map.clone(),
+                        FuzzOperand::Val(FakeVal(v.get_payload())),
+                    ))
+                }
+                TypedModMapPrototype::MapKeyByPos(v) => {
+                    TypedFuzzInstruction::Map(TypedModMap::MapKeyByPos(map.clone(), *v))
+                }
+                TypedModMapPrototype::MapKeys => {
+                    TypedFuzzInstruction::Map(TypedModMap::MapKeys(map.clone()))
+                }
+                TypedModMapPrototype::MapLen => {
+                    TypedFuzzInstruction::Map(TypedModMap::MapLen(map.clone()))
+                }
+                TypedModMapPrototype::MapNew => TypedFuzzInstruction::Map(TypedModMap::MapNew),
+                TypedModMapPrototype::MapNewFromLinearMemory(v_0, v_1, v_2) => {
+                    TypedFuzzInstruction::Map(TypedModMap::MapNewFromLinearMemory(
+                        v_0.to_guest(env),
+                        v_1.to_guest(env),
+                        *v_2,
+                    ))
+                }
+                TypedModMapPrototype::MapPut(v_0, v_1) => {
+                    let v_0 = Val::from_val(env, v_0);
+                    let v_1 = Val::from_val(env, v_1);
+                    TypedFuzzInstruction::Map(TypedModMap::MapPut(
+                        map.clone(),
+                        FuzzOperand::Val(FakeVal(v_0.get_payload())),
+                        FuzzOperand::Val(FakeVal(v_1.get_payload())),
+                    ))
+                }
+                TypedModMapPrototype::MapUnpackToLinearMemory(v_0, v_1, v_2) => {
+                    TypedFuzzInstruction::Map(TypedModMap::MapUnpackToLinearMemory(
+                        map.clone(),
+                        v_0.to_guest(env),
+                        v_1.to_guest(env),
+                        *v_2,
+                    ))
+                }
+                TypedModMapPrototype::MapValByPos(v) => {
+                    TypedFuzzInstruction::Map(TypedModMap::MapValByPos(map.clone(), *v))
+                }
+                TypedModMapPrototype::MapValues => {
+                    TypedFuzzInstruction::Map(TypedModMap::MapValues(map.clone()))
+                }
+            }
+        }
+    }
+
+    impl TypedModMapPrototype {
+        /// Whether the outcome depends on guest memory, which the model doesn't track.
+        pub fn uses_linear_memory(&self) -> bool {
+            matches!(
+                self,
+                TypedModMapPrototype::MapNewFromLinearMemory(..)
+                    | TypedModMapPrototype::MapUnpackToLinearMemory(..)
+            )
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! The comparison `diff_fuzz` makes: an instruction run by the contract's
+//! wasm build and by the contract registered natively must do the same.
+
+use crate::budget::{assert_same_charges, exceeded_budget, BudgetOracle, Cost};
+use crate::fuzzcontract::{self, FuzzContract};
+use crate::{fuzz_contract_address, FuzzInstructionPrototype, FUZZ_CONTRACTS};
+use soroban_sdk::arbitrary::fuzz_catch_panic;
+use soroban_sdk::testutils::Events;
+use soroban_sdk::xdr::{
+    ContractDataDurability, ContractDataEntryBody, ContractDataEntryData, LedgerEntryData, ScVal,
+};
+use soroban_sdk::{Address, Env, Error, TryFromVal};
+
+const WASM: &[u8] =
+    include_bytes!("../../target/wasm32-unknown-unknown/release/contract_for_fuzz.wasm");
+
+/// What running one instruction did, as XDR so that the outcomes of two
+/// `Env`s can be compared.
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    result: Result<ScVal, Result<Error, std::string::String>>,
+    events: ScVal,
+    storage: std::vec::Vec<(ScVal, ContractDataDurability, ScVal, u32)>,
+}
+
+impl Outcome {
+    /// Whether the call ran out of budget. The VM charges the budget for its
+    /// own work as well, so the two sides run out at different points.
+    fn exceeded_budget(&self) -> bool {
+        matches!(&self.result, Err(Ok(e)) if exceeded_budget(e))
+    }
+}
+
+/// Runs the instruction in a fresh `Env` against the contract `register`
+/// installs, and returns what the call charged, with its outcome or `None`
+/// if the host panicked, either during the call or while its effects were
+/// being read back.
+fn run(
+    register: impl FnOnce(&Env) -> Address,
+    input: &FuzzInstructionPrototype,
+    input_len: usize,
+) -> (Option<Outcome>, std::vec::Vec<Cost>) {
+    let env = Env::default();
+
+    let contract_id = register(&env);
+    let client = fuzzcontract::Client::new(&env, &contract_id);
+    let mut budget = BudgetOracle::new(&env, &client);
+    let fuzz_instruction = input.to_guest(&env);
+
+    let call_r = budget.call(&env, || {
+        fuzz_catch_panic(|| client.try_run(&fuzz_instruction))
+    });
+    // The two sides charge differently, so each is only checked on its own.
+    let charges = budget.finish(input_len);
+    let Ok(call_r) = call_r else {
+        return (None, charges);
+    };
+
+    let outcome = fuzz_catch_panic(|| {
+        let result = match call_r {
+            Ok(v) => Ok(ScVal::try_from_val(&env, &v.unwrap()).unwrap()),
+            Err(e) => Err(e.map_err(|e| format!("{e:?}"))),
+        };
+        let events = ScVal::try_from_val(&env, &env.events().all().to_val()).unwrap();
+
+        Outcome {
+            result,
+            events,
+            storage: contract_data(&env),
+        }
+    })
+    .ok();
+
+    (outcome, charges)
+}
+
+/// Runs the instruction twice against the same build, checking that both
+/// runs charged the same, and returns the outcome.
+fn run_twice(
+    register: impl Fn(&Env) -> Address,
+    input: &FuzzInstructionPrototype,
+    input_len: usize,
+) -> Option<Outcome> {
+    let (outcome, charges) = run(&register, input, input_len);
+    assert_same_charges(&charges, &run(&register, input, input_len).1);
+    outcome
+}
+
+/// Lists the contract data in `env`'s ledger. Contract instances are reduced
+/// to their storage, since a native contract's instance names a different
+/// executable than its wasm build's.
+fn contract_data(env: &Env) -> std::vec::Vec<(ScVal, ContractDataDurability, ScVal, u32)> {
+    env.to_snapshot()
+        .ledger_entries
+        .into_iter()
+        .filter_map(|(_, entry)| match entry.data {
+            LedgerEntryData::ContractData(data) => {
+                let val = match data.body {
+                    ContractDataEntryBody::DataEntry(ContractDataEntryData {
+                        val: ScVal::ContractInstance(instance),
+                        ..
+                    }) => ScVal::Map(instance.storage),
+                    ContractDataEntryBody::DataEntry(ContractDataEntryData { val, .. }) => val,
+                    ContractDataEntryBody::ExpirationExtension => ScVal::Void,
+                };
+                Some((data.key, data.durability, val, data.expiration_ledger_seq))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Runs the instruction against both builds and asserts that they agree.
+///
+/// Instructions whose host function takes guest linear memory are skipped:
+/// a natively registered contract runs in a frame with no guest memory, so
+/// the host refuses them there whatever they would do under wasm.
+pub fn check(input: &FuzzInstructionPrototype, input_len: usize) {
+    check_builds(register_wasm, register_native, input, input_len);
+}
+
+/// Registers the copies of the contract's wasm build at the addresses
+/// `register_fuzz_contract` gives them, so that calls into the other copies
+/// reach them, and returns the address of the first.
+fn register_wasm(env: &Env) -> Address {
+    for i in 0..FUZZ_CONTRACTS {
+        env.register_contract_wasm(&fuzz_contract_address(env, i), WASM);
+    }
+    fuzz_contract_address(env, 0)
+}
+
+/// Registers the copies of the contract natively; see `register_wasm`.
+fn register_native(env: &Env) -> Address {
+    for i in 0..FUZZ_CONTRACTS {
+        env.register_contract(&fuzz_contract_address(env, i), FuzzContract);
+    }
+    fuzz_contract_address(env, 0)
+}
+
+/// Runs the instruction against the contracts `wasm` and `native` install,
+/// and asserts that they agree; see `check`.
+fn check_builds(
+    wasm: impl Fn(&Env) -> Address,
+    native: impl Fn(&Env) -> Address,
+    input: &FuzzInstructionPrototype,
+    input_len: usize,
+) {
+    if input.innermost().host_function().uses_linear_memory() {
+        return;
+    }
+
+    let wasm = run_twice(wasm, input, input_len);
+    let native = run_twice(native, input, input_len);
+
+    if [&wasm, &native]
+        .iter()
+        .any(|o| o.as_ref().is_some_and(Outcome::exceeded_budget))
+    {
+        return;
+    }
+
+    // Panics are reported by the other targets; here the two only have to
+    // agree.
+    assert_eq!(wasm, native, "wasm and native execution diverged");
+}
+
+// These need the contract's wasm build, like `diff_fuzz` itself.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typed::{
+        BytesOperandPrototype, TypedFuzzInstructionPrototype, TypedModBufPrototype,
+    };
+    use crate::FuzzPointerPrototype;
+    use soroban_sdk::arbitrary::arbitrary::{Arbitrary, Unstructured};
+
+    #[test]
+    fn bytes_copy_to_linear_memory_is_skipped() {
+        let bytes = Arbitrary::arbitrary(&mut Unstructured::new(&[4, 1, 2, 3, 4])).unwrap();
+        let scratch = Arbitrary::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        let input = FuzzInstructionPrototype::Typed(TypedFuzzInstructionPrototype::Buf(
+            TypedModBufPrototype::BytesCopyToLinearMemory(
+                BytesOperandPrototype::Val(bytes),
+                0,
+                FuzzPointerPrototype::Scratch(0, scratch),
+                4,
+            ),
+        ));
+        assert!(input.host_function().uses_linear_memory());
+
+        let unreachable = |_: &Env| -> Address { panic!("a skipped instruction was run") };
+        check_builds(unreachable, unreachable, &input, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "wasm and native execution diverged")]
+    fn diverging_storage_is_reported() {
+        let bytes = Arbitrary::arbitrary(&mut Unstructured::new(&[4, 1, 2, 3, 4])).unwrap();
+        let input = FuzzInstructionPrototype::Typed(TypedFuzzInstructionPrototype::Buf(
+            TypedModBufPrototype::BytesLen(BytesOperandPrototype::Val(bytes)),
+        ));
+        assert!(!input.host_function().uses_linear_memory());
+
+        // The native side starts with an entry the wasm side doesn't have.
+        check_builds(
+            register_wasm,
+            |env| {
+                let contract_id = register_native(env);
+                env.as_contract(&contract_id, || {
+                    env.storage().persistent().set(&0u32, &0u32)
+                });
+                contract_id
+            },
+            &input,
+            0,
+        );
+    }
+}
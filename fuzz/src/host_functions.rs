@@ -0,0 +1,101 @@
+//! The host functions of the env interface, as listed by
+//! `call_macro_with_all_host_functions`, with the names and types of their
+//! arguments.
+
+use soroban_env_common::call_macro_with_all_host_functions;
+
+/// The names the host functions give their arguments that point into guest
+/// linear memory. The contract's instructions take a `FuzzPointer` for each.
+pub const LINEAR_MEMORY_ARGS: &[&str] =
+    &["lm_pos", "msg_pos", "vals_pos", "keys_pos", "slices_pos"];
+
+/// Host functions whose camel-cased names are over the 32 character limit on
+/// contract spec variant names, and the names raw.rs and typed.rs give them.
+const SHORTENED_NAMES: &[(&str, &str)] = &[(
+    "bump_current_contract_instance_and_code",
+    "BumpCurrentContract",
+)];
+
+pub struct HostFunction {
+    pub module: &'static str,
+    pub name: &'static str,
+    /// The name and type of each argument.
+    pub args: &'static [(&'static str, &'static str)],
+}
+
+impl HostFunction {
+    /// The name of the instruction variants that call this host function.
+    pub fn variant_name(&self) -> String {
+        SHORTENED_NAMES
+            .iter()
+            .find(|(name, _)| *name == self.name)
+            .map(|(_, short)| short.to_string())
+            .unwrap_or_else(|| camel_case(self.name))
+    }
+
+    /// Whether any argument points into guest linear memory, which only a
+    /// wasm guest has.
+    pub fn uses_linear_memory(&self) -> bool {
+        self.args
+            .iter()
+            .any(|(name, _)| LINEAR_MEMORY_ARGS.contains(name))
+    }
+}
+
+pub fn camel_case(s: &str) -> String {
+    s.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(c) => c.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// The host function the x-macro lists as `name` in `module`, for the
+/// `host_function` methods raw.rs and typed.rs generate from the same list.
+pub fn named(module: &str, name: &str) -> &'static HostFunction {
+    HOST_FUNCTIONS
+        .iter()
+        .find(|host_fn| host_fn.module == module && host_fn.name == name)
+        .expect("the x-macro lists every host function")
+}
+
+// This is a callback macro that pattern-matches the token-tree passed by the
+// x-macro (call_macro_with_all_host_functions) and produces a flat list of
+// every host function with its module and arguments.
+macro_rules! generate_host_function_list {
+    {
+        $(
+            $(#[$mod_attr:meta])*
+            mod $mod_id:ident $mod_str:literal
+            {
+                $(
+                    $(#[$fn_attr:meta])*
+                    { $fn_str:literal, fn $fn_id:ident ($($arg:ident : $type:ty),*) -> $ret:ty }
+                )*
+            }
+        )*
+    }
+
+    =>
+
+    {
+        pub const HOST_FUNCTIONS: &[HostFunction] = &[
+            $(
+                $(
+                    HostFunction {
+                        module: stringify!($mod_id),
+                        name: stringify!($fn_id),
+                        args: &[$((stringify!($arg), stringify!($type))),*],
+                    },
+                )*
+            )*
+        ];
+    };
+}
+
+// Here we invoke the x-macro passing generate_host_function_list as its callback macro.
+call_macro_with_all_host_functions! { generate_host_function_list }
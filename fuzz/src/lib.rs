@@ -0,0 +1,212 @@
+// The prototype generators munch every host function one argument at a
+// time, which nests deeper than the default limit.
+#![recursion_limit = "1024"]
+
+use core::any::Any;
+use fuzzcontract::*;
+use host_functions::{camel_case, HostFunction};
+use raw::RawFuzzInstructionPrototype;
+use soroban_sdk::arbitrary::arbitrary::{self, Arbitrary, Unstructured};
+use soroban_sdk::arbitrary::SorobanArbitrary;
+use soroban_sdk::{vec, Address, Bytes, Env, FromVal, IntoVal, Symbol, Val};
+use typed::{TypedFuzzInstructionPrototype, TypedModCallPrototype};
+
+pub mod budget;
+pub mod collections;
+#[cfg(feature = "native")]
+pub mod diff;
+pub mod host_functions;
+pub mod mutator;
+pub mod program;
+pub mod raw;
+pub mod roundtrip;
+pub mod scratch;
+pub mod scval;
+pub mod typed;
+
+#[cfg(not(feature = "native"))]
+pub mod fuzzcontract {
+    soroban_sdk::contractimport!(
+        file = "../target/wasm32-unknown-unknown/release/contract_for_fuzz.wasm"
+    );
+}
+
+// With the `native` feature the contract is linked in and registered
+// natively, so the same inputs run without going through wasm.
+#[cfg(feature = "native")]
+pub mod fuzzcontract {
+    pub use contract_for_fuzz::FuzzContractClient as Client;
+    pub use contract_for_fuzz::{raw::*, roundtrip::*, scratch::ScratchWrite, typed::*, *};
+}
+
+// Shared with the contract's regression tests, which register their copies
+// of the contract the same way.
+pub use contract_for_fuzz::{fuzz_contract_address, FUZZ_CONTRACTS};
+
+/// Registers the copies of `FuzzContract` with `env`, and returns the address
+/// of the first, which the targets call: its wasm build, or with the
+/// `native` feature the contract itself.
+pub fn register_fuzz_contract(env: &Env) -> Address {
+    for i in 0..FUZZ_CONTRACTS {
+        let contract_id = fuzz_contract_address(env, i);
+        #[cfg(not(feature = "native"))]
+        env.register_contract_wasm(&contract_id, fuzzcontract::WASM);
+        #[cfg(feature = "native")]
+        env.register_contract(&contract_id, fuzzcontract::FuzzContract);
+    }
+
+    fuzz_contract_address(env, 0)
+}
+
+/// Either kind of instruction, for targets that fuzz both.
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum FuzzInstructionPrototype {
+    Raw(RawFuzzInstructionPrototype),
+    Typed(TypedFuzzInstructionPrototype),
+}
+
+impl FuzzInstructionPrototype {
+    pub fn to_guest(&self, env: &Env) -> FuzzInstruction {
+        match self {
+            FuzzInstructionPrototype::Raw(v) => FuzzInstruction::Raw(v.to_guest(env)),
+            FuzzInstructionPrototype::Typed(v) => FuzzInstruction::Typed(v.to_guest(env)),
+        }
+    }
+
+    /// The host function this instruction calls. An instruction that calls
+    /// into another copy of the contract calls `call` or `try_call`; see
+    /// `innermost` for the instruction it runs there.
+    pub fn host_function(&self) -> &'static HostFunction {
+        match self {
+            FuzzInstructionPrototype::Raw(v) => v.host_function(),
+            FuzzInstructionPrototype::Typed(v) => v.host_function(),
+        }
+    }
+
+    /// The instruction's kind, module and variant, such as
+    /// `Typed::Buf::BytesCopyToLinearMemory`.
+    pub fn name(&self) -> String {
+        let kind = match self {
+            FuzzInstructionPrototype::Raw(_) => "Raw",
+            FuzzInstructionPrototype::Typed(_) => "Typed",
+        };
+        let host_fn = self.host_function();
+        format!(
+            "{kind}::{}::{}",
+            camel_case(host_fn.module),
+            host_fn.variant_name()
+        )
+    }
+
+    /// The instruction whose host function runs last: the instruction this
+    /// one passes to another copy of the contract, if it calls into one,
+    /// followed down any further calls.
+    pub fn innermost(&self) -> &FuzzInstructionPrototype {
+        match self {
+            FuzzInstructionPrototype::Typed(TypedFuzzInstructionPrototype::Call(
+                TypedModCallPrototype::Call(FuzzCallPrototype::Run(_, instr))
+                | TypedModCallPrototype::TryCall(FuzzCallPrototype::Run(_, instr)),
+            )) => instr.innermost(),
+            _ => self,
+        }
+    }
+}
+
+/// Decodes an input of `rawval_fuzz` or `typed_fuzz`, as named by `target`,
+/// into the program the target runs for it.
+pub fn decode_program(target: &str, data: &[u8]) -> Result<Vec<FuzzInstructionPrototype>, String> {
+    let u = Unstructured::new(data);
+    let decoded = match target {
+        "rawval_fuzz" => Vec::<RawFuzzInstructionPrototype>::arbitrary_take_rest(u)
+            .map(|v| v.into_iter().map(FuzzInstructionPrototype::Raw).collect()),
+        "typed_fuzz" => Vec::<TypedFuzzInstructionPrototype>::arbitrary_take_rest(u)
+            .map(|v| v.into_iter().map(FuzzInstructionPrototype::Typed).collect()),
+        _ => return Err(format!("unknown target {target}")),
+    };
+    decoded.map_err(|e| format!("{target} would not run this input: {e}"))
+}
+
+/// The message of a panic caught by `fuzz_catch_panic`.
+pub fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    if let Some(msg) = panic.downcast_ref::<String>() {
+        msg
+    } else if let Some(msg) = panic.downcast_ref::<&str>() {
+        msg
+    } else {
+        "non-string panic payload"
+    }
+}
+
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum FuzzPointerPrototype {
+    Scratch(u32, <Bytes as SorobanArbitrary>::Prototype),
+    Wild(u32),
+}
+
+impl FuzzPointerPrototype {
+    pub fn to_guest(&self, env: &Env) -> FuzzPointer {
+        match self {
+            FuzzPointerPrototype::Scratch(offset, bytes) => {
+                FuzzPointer::Scratch(*offset, Bytes::from_val(env, bytes))
+            }
+            FuzzPointerPrototype::Wild(pos) => FuzzPointer::Wild(*pos),
+        }
+    }
+}
+
+/// The arguments of a `call` or `try_call`.
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum FuzzCallPrototype {
+    /// Calls `run` on a copy of `FuzzContract` other than the first, picked
+    /// by its index wrapped to their number, with an instruction for it to
+    /// run. The first copy is the one the targets call, which the host
+    /// refuses to re-enter, so it is never picked.
+    Run(u8, Box<FuzzInstructionPrototype>),
+    Any(
+        <Address as SorobanArbitrary>::Prototype,
+        <Symbol as SorobanArbitrary>::Prototype,
+        <soroban_sdk::Vec<Val> as SorobanArbitrary>::Prototype,
+    ),
+}
+
+impl FuzzCallPrototype {
+    pub fn contract(&self, env: &Env) -> Address {
+        match self {
+            FuzzCallPrototype::Run(i, _) => {
+                fuzz_contract_address(env, 1 + i % (FUZZ_CONTRACTS - 1))
+            }
+            FuzzCallPrototype::Any(contract, _, _) => Address::from_val(env, contract),
+        }
+    }
+
+    pub fn func(&self, env: &Env) -> Symbol {
+        match self {
+            FuzzCallPrototype::Run(_, _) => Symbol::new(env, "run"),
+            FuzzCallPrototype::Any(_, func, _) => Symbol::from_val(env, func),
+        }
+    }
+
+    pub fn args(&self, env: &Env) -> soroban_sdk::Vec<Val> {
+        match self {
+            FuzzCallPrototype::Run(_, instr) => vec![env, instr.to_guest(env).into_val(env)],
+            FuzzCallPrototype::Any(_, _, args) => soroban_sdk::Vec::<Val>::from_val(env, args),
+        }
+    }
+}
+
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum FuzzStorageTypePrototype {
+    Temporary,
+    Persistent,
+    Instance,
+}
+
+impl FuzzStorageTypePrototype {
+    pub fn to_guest(&self) -> FuzzStorageType {
+        match self {
+            FuzzStorageTypePrototype::Temporary => FuzzStorageType::Temporary,
+            FuzzStorageTypePrototype::Persistent => FuzzStorageType::Persistent,
+            FuzzStorageTypePrototype::Instance => FuzzStorageType::Instance,
+        }
+    }
+}
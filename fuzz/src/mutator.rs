@@ -0,0 +1,517 @@
+//! A structure-aware mutator for the inputs of `rawval_fuzz` and
+//! `typed_fuzz`.
+//!
+//! libFuzzer's own mutations work on the bytes `arbitrary` decodes, so a
+//! flipped bit in a selector picks another module or host function and
+//! leaves every operand after it decoded out of step. This mutator instead
+//! finds where each instruction's selectors and operands lie in the input
+//! and edits one of them: it swaps the host function for another of the
+//! same module, mutates one operand, splices an operand from another
+//! instruction, or replaces a `Val` operand with one of a different tag.
+
+use crate::raw::{FuzzOperandPrototype, RawValPrototype};
+use crate::typed::ValOperandPrototype;
+use core::mem::{discriminant, Discriminant};
+use core::ops::Range;
+use soroban_sdk::arbitrary::arbitrary::{Arbitrary, Unstructured};
+use soroban_sdk::arbitrary::SorobanArbitrary;
+use soroban_sdk::Val;
+
+/// The instructions of each input that get a structured mutation, out of
+/// four; the rest get one of libFuzzer's own.
+const STRUCTURED_MUTATIONS: usize = 3;
+/// Attempts at a `Val` operand of a different tag.
+const VAL_ATTEMPTS: usize = 8;
+/// The most bytes a replacement `Val` operand is decoded from.
+const MAX_VAL_LEN: usize = 40;
+
+type ValPrototype = <Val as SorobanArbitrary>::Prototype;
+
+/// The part of an input that is still to be decoded: `arbitrary` reads
+/// most values from the front, but some lengths from the back.
+#[derive(Clone)]
+pub struct Cursor<'a> {
+    data: &'a [u8],
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(data: &'a [u8]) -> Cursor<'a> {
+        Cursor {
+            data,
+            front: 0,
+            back: data.len(),
+        }
+    }
+
+    /// Decodes a `T`, returning it and the bytes it read from the front.
+    pub fn decode<T: Arbitrary<'a>>(&mut self) -> Option<(T, Range<usize>)> {
+        self.decode_span().map(|(v, span)| (v, span.front))
+    }
+
+    /// Decodes a `T`, returning it and the bytes it read from each end.
+    pub fn decode_span<T: Arbitrary<'a>>(&mut self) -> Option<(T, Span)> {
+        let (front, back) = (self.front, self.back);
+        let mut u = Unstructured::new(&self.data[self.front..self.back]);
+        let v = T::arbitrary(&mut u).ok()?;
+
+        let rest = u.peek_bytes(u.len()).unwrap();
+        if rest.is_empty() {
+            // Which of the bytes came from the back cannot be told, so all
+            // of them count as having done so.
+            self.front = self.back;
+            return Some((
+                v,
+                Span {
+                    front: front..back,
+                    back: front..back,
+                },
+            ));
+        }
+        self.front = rest.as_ptr() as usize - self.data.as_ptr() as usize;
+        self.back = self.front + rest.len();
+        Some((
+            v,
+            Span {
+                front: front..self.front,
+                back: self.back..back,
+            },
+        ))
+    }
+}
+
+/// The bytes a value was decoded from: most from the front of what was left
+/// of the input, and the lengths of some collections from the back. A value
+/// that used up the input counts as having read all of it from both ends.
+#[derive(Clone, Debug)]
+pub struct Span {
+    pub front: Range<usize>,
+    pub back: Range<usize>,
+}
+
+/// Where an operand lies in an input, and the type of its prototype.
+#[derive(Clone, Debug)]
+pub struct OperandSpan {
+    pub range: Range<usize>,
+    /// The lengths it read from the back of the input, which `arbitrary`
+    /// sizes by how much of the input is left, so that they mean something
+    /// else anywhere else in it.
+    pub back: Range<usize>,
+    pub type_name: &'static str,
+}
+
+impl OperandSpan {
+    /// Whether the operand was decoded from its `range` alone, so that those
+    /// bytes decode to it wherever they are put.
+    fn is_self_contained(&self) -> bool {
+        self.back.is_empty()
+    }
+}
+
+/// The operands of a prototype variant, as a tuple of their prototypes.
+pub trait Operands {
+    fn spans(cursor: &mut Cursor) -> Vec<OperandSpan>;
+}
+
+impl Operands for () {
+    fn spans(_cursor: &mut Cursor) -> Vec<OperandSpan> {
+        Vec::new()
+    }
+}
+
+macro_rules! impl_operands_for_tuples {
+    ($(($($t:ident),+))*) => {
+        $(
+            impl<$($t),+> Operands for ($($t,)+)
+            where
+                $($t: for<'a> Arbitrary<'a>,)+
+            {
+                fn spans(cursor: &mut Cursor) -> Vec<OperandSpan> {
+                    let mut spans = Vec::new();
+                    $(
+                        let Some((_, span)) = cursor.decode_span::<$t>() else {
+                            return spans;
+                        };
+                        spans.push(OperandSpan {
+                            range: span.front,
+                            back: span.back,
+                            type_name: core::any::type_name::<$t>(),
+                        });
+                    )+
+                    spans
+                }
+            }
+        )*
+    };
+}
+
+impl_operands_for_tuples! {
+    (A)
+    (A, B)
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+}
+
+/// `RawFuzzInstructionPrototype` or `TypedFuzzInstructionPrototype`.
+pub trait Instruction: for<'a> Arbitrary<'a> {
+    /// Where the operands of this instruction lie, with `cursor` at the
+    /// first of them.
+    fn operand_spans(&self, cursor: &mut Cursor) -> Vec<OperandSpan>;
+}
+
+/// Where an instruction of an input lies.
+struct InstructionSpans {
+    /// The `u32`s that pick the module and the host function.
+    selectors: Range<usize>,
+    operands: Vec<OperandSpan>,
+}
+
+/// Finds the instructions of an input of `rawval_fuzz` or `typed_fuzz`,
+/// which decode it as a `Vec<I>` with `arbitrary_take_rest`.
+fn instructions<I: Instruction>(data: &[u8]) -> Vec<InstructionSpans> {
+    let mut cursor = Cursor::new(data);
+    let mut instructions = Vec::new();
+    while let Some((true, _)) = cursor.decode::<bool>() {
+        let mut operands = cursor.clone();
+        let Some((instr, _)) = cursor.decode::<I>() else {
+            break;
+        };
+        let start = operands.front;
+        if operands.decode::<(u32, u32)>().is_none() {
+            break;
+        }
+        instructions.push(InstructionSpans {
+            selectors: start..operands.front,
+            operands: instr.operand_spans(&mut operands),
+        });
+    }
+    instructions
+}
+
+/// A deterministic xorshift generator, seeded by libFuzzer.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        (!items.is_empty()).then(|| &items[self.below(items.len())])
+    }
+}
+
+/// A replacement of some bytes of an input.
+struct Edit {
+    range: Range<usize>,
+    bytes: Vec<u8>,
+}
+
+impl Edit {
+    /// Makes the edit to the first `size` bytes of `data`, which must have
+    /// room for the result, and returns its size.
+    fn apply(&self, data: &mut [u8], size: usize) -> usize {
+        let mut edited = data[..self.range.start].to_vec();
+        edited.extend_from_slice(&self.bytes);
+        edited.extend_from_slice(&data[self.range.end..size]);
+        data[..edited.len()].copy_from_slice(&edited);
+        edited.len()
+    }
+}
+
+/// Picks another host function of the same module.
+fn swap_host_function(rng: &mut Rng, instructions: &[InstructionSpans]) -> Option<Edit> {
+    let instr = rng.pick(instructions)?;
+    let function = instr.selectors.start + 4..instr.selectors.end;
+    (function.len() == 4).then(|| Edit {
+        range: function,
+        bytes: rng.bytes(4),
+    })
+}
+
+/// Mutates the bytes of one operand with libFuzzer's own mutations.
+fn mutate_operand(
+    rng: &mut Rng,
+    data: &[u8],
+    instructions: &[InstructionSpans],
+    max_size: usize,
+) -> Option<Edit> {
+    let instr = rng.pick(instructions)?;
+    let operand = rng.pick(&instr.operands)?.clone();
+    let len = operand.range.len();
+    let max_len = (max_size + len).saturating_sub(data.len()).max(len);
+    let mut bytes = data[operand.range.clone()].to_vec();
+    bytes.resize(max_len, 0);
+    let len = libfuzzer_sys::fuzzer_mutate(&mut bytes, len, max_len);
+    bytes.truncate(len);
+    Some(Edit {
+        range: operand.range,
+        bytes,
+    })
+}
+
+/// Replaces an operand with one from another instruction, of the same type
+/// if there is one. Only operands decoded from their own bytes alone are
+/// spliced, into the place of another such.
+fn splice_operand(rng: &mut Rng, data: &[u8], instructions: &[InstructionSpans]) -> Option<Edit> {
+    let self_contained = |i: &InstructionSpans| -> Vec<OperandSpan> {
+        i.operands
+            .iter()
+            .filter(|o| o.is_self_contained())
+            .cloned()
+            .collect()
+    };
+    let targets = self_contained(rng.pick(instructions)?);
+    let target = rng.pick(&targets)?.clone();
+    let sources = self_contained(rng.pick(instructions)?);
+    let same_type: Vec<&OperandSpan> = sources
+        .iter()
+        .filter(|o| o.type_name == target.type_name)
+        .collect();
+    let source = match rng.pick(&same_type) {
+        Some(source) => (*source).clone(),
+        None => rng.pick(&sources)?.clone(),
+    };
+    Some(Edit {
+        range: target.range,
+        bytes: data[source.range].to_vec(),
+    })
+}
+
+/// Which kind of `Val` an operand is, told apart by the variant of its
+/// prototype. Each kind of `RawValPrototype` counts as a tag of its own.
+#[derive(PartialEq)]
+enum ValTag {
+    Val(Discriminant<ValPrototype>),
+    RawVal(Discriminant<RawValPrototype>),
+}
+
+/// The prototype of an operand that may be a `Val`.
+trait ValOperand {
+    /// The tag of the `Val`, or `None` if the operand names a register.
+    fn tag(&self) -> Option<ValTag>;
+}
+
+impl ValOperand for ValPrototype {
+    fn tag(&self) -> Option<ValTag> {
+        Some(ValTag::Val(discriminant(self)))
+    }
+}
+
+impl ValOperand for FuzzOperandPrototype {
+    fn tag(&self) -> Option<ValTag> {
+        match self {
+            FuzzOperandPrototype::Val(v) => v.tag(),
+            FuzzOperandPrototype::RawVal(v) => Some(ValTag::RawVal(discriminant(v))),
+            FuzzOperandPrototype::Reg(_) => None,
+        }
+    }
+}
+
+impl ValOperand for ValOperandPrototype {
+    fn tag(&self) -> Option<ValTag> {
+        match self {
+            ValOperandPrototype::Val(v) => v.tag(),
+            ValOperandPrototype::Reg(_) => None,
+        }
+    }
+}
+
+/// The tag of a `Val` operand and the number of bytes it was decoded from,
+/// or `None` if it is not a `Val` or reads from the back of `bytes`.
+fn val_tag<T: for<'a> Arbitrary<'a> + ValOperand>(bytes: &[u8]) -> Option<(ValTag, usize)> {
+    let (v, span) = Cursor::new(bytes).decode_span::<T>()?;
+    if !span.back.is_empty() {
+        return None;
+    }
+    Some((v.tag()?, span.front.len()))
+}
+
+/// Replaces a `Val` operand with one of a different tag. As with splicing,
+/// both the operand and its replacement must be decoded from their own
+/// bytes alone.
+fn replace_val(rng: &mut Rng, data: &[u8], instructions: &[InstructionSpans]) -> Option<Edit> {
+    let is_val = |o: &&OperandSpan| {
+        o.is_self_contained()
+            && (o.type_name == core::any::type_name::<ValPrototype>()
+                || o.type_name == core::any::type_name::<FuzzOperandPrototype>()
+                || o.type_name == core::any::type_name::<ValOperandPrototype>())
+    };
+    let vals: Vec<&OperandSpan> = instructions
+        .iter()
+        .flat_map(|i| i.operands.iter())
+        .filter(is_val)
+        .collect();
+    let operand = (*rng.pick(&vals)?).clone();
+    let tag_of = |bytes: &[u8]| {
+        if operand.type_name == core::any::type_name::<ValPrototype>() {
+            val_tag::<ValPrototype>(bytes)
+        } else if operand.type_name == core::any::type_name::<ValOperandPrototype>() {
+            val_tag::<ValOperandPrototype>(bytes)
+        } else {
+            val_tag::<FuzzOperandPrototype>(bytes)
+        }
+    };
+
+    let tag = tag_of(&data[operand.range.clone()]).map(|(tag, _)| tag);
+    (0..VAL_ATTEMPTS).find_map(|_| {
+        let len = 4 + rng.below(MAX_VAL_LEN - 4);
+        let mut bytes = rng.bytes(len);
+        let (new_tag, len) = tag_of(&bytes)?;
+        bytes.truncate(len);
+        (Some(&new_tag) != tag.as_ref()).then(|| Edit {
+            range: operand.range.clone(),
+            bytes,
+        })
+    })
+}
+
+/// Mutates an input of `rawval_fuzz` or `typed_fuzz`, whose instructions
+/// are `I`s, in place, as a `fuzz_mutator!` does.
+pub fn mutate<I: Instruction>(data: &mut [u8], size: usize, max_size: usize, seed: u32) -> usize {
+    let mut rng = Rng(u64::from(seed) << 32 | 0x9e37_79b9);
+    let instructions = instructions::<I>(&data[..size]);
+
+    let edit = match rng.below(4) {
+        _ if instructions.is_empty() => None,
+        n if n >= STRUCTURED_MUTATIONS => None,
+        _ => match rng.below(4) {
+            0 => swap_host_function(&mut rng, &instructions),
+            1 => mutate_operand(&mut rng, &data[..size], &instructions, max_size),
+            2 => splice_operand(&mut rng, &data[..size], &instructions),
+            _ => replace_val(&mut rng, &data[..size], &instructions),
+        },
+    };
+    let Some(edit) = edit.filter(|e| size - e.range.len() + e.bytes.len() <= max_size) else {
+        return libfuzzer_sys::fuzzer_mutate(data, size, max_size);
+    };
+
+    edit.apply(data, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::RawFuzzInstructionPrototype;
+
+    /// The module and function selectors of the raw instruction that calls
+    /// the host function `name`.
+    fn selectors(name: &str) -> Vec<u8> {
+        let picks = (0..=u8::MAX).map(|i| u32::from(i) << 24);
+        for module in picks.clone() {
+            for function in picks.clone() {
+                let mut bytes = [module.to_le_bytes(), function.to_le_bytes()].concat();
+                let selectors = bytes.clone();
+                bytes.resize(32, 0);
+                let instr =
+                    RawFuzzInstructionPrototype::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+                if instr.host_function().name == name {
+                    return selectors;
+                }
+            }
+        }
+        panic!("no raw instruction calls {name}");
+    }
+
+    fn ranges(instr: &InstructionSpans) -> Vec<Range<usize>> {
+        instr.operands.iter().map(|o| o.range.clone()).collect()
+    }
+
+    /// `obj_from_u64(5)` then `obj_to_u64` of register 7, each behind the
+    /// byte that says it follows, and a last byte that says no more do.
+    fn program() -> Vec<u8> {
+        let reg = ((2u64 << 32).div_ceil(3) as u32).to_le_bytes();
+        [
+            &[1][..],
+            &selectors("obj_from_u64"),
+            &5u64.to_le_bytes(),
+            &[1],
+            &selectors("obj_to_u64"),
+            &reg,
+            &7u32.to_le_bytes(),
+            &[0],
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn decode_span_tells_the_front_from_the_back() {
+        // A slice reads its length from the last byte, and then that many
+        // bytes from the front.
+        let data = [1, 0, 0, 0, 10, 11, 12, 2];
+        let mut cursor = Cursor::new(&data);
+
+        let (v, span) = cursor.decode_span::<u32>().unwrap();
+        assert_eq!(v, 1);
+        assert_eq!((span.front, span.back), (0..4, 8..8));
+
+        let (v, span) = cursor.decode_span::<&[u8]>().unwrap();
+        assert_eq!(v, [10, 11]);
+        assert_eq!((span.front, span.back), (4..6, 7..8));
+
+        // The last byte used up what was left.
+        let (_, span) = cursor.decode_span::<u8>().unwrap();
+        assert_eq!((span.front, span.back), (6..7, 6..7));
+    }
+
+    #[test]
+    fn instructions_cover_their_selectors_and_operands() {
+        let data = program();
+        let instructions = instructions::<RawFuzzInstructionPrototype>(&data);
+
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].selectors, 1..9);
+        assert_eq!(ranges(&instructions[0]), [9..17]);
+        assert_eq!(instructions[1].selectors, 18..26);
+        assert_eq!(ranges(&instructions[1]), [26..34]);
+        assert!(instructions
+            .iter()
+            .flat_map(|i| i.operands.iter())
+            .all(OperandSpan::is_self_contained));
+    }
+
+    #[test]
+    fn swapping_a_host_function_edits_only_its_selector() {
+        let data = program();
+        let instructions = instructions::<RawFuzzInstructionPrototype>(&data);
+
+        for seed in 1..16 {
+            let edit = swap_host_function(&mut Rng(seed), &instructions).unwrap();
+            assert!(edit.range == (5..9) || edit.range == (22..26));
+            assert_eq!(edit.bytes.len(), 4);
+        }
+    }
+
+    #[test]
+    fn splicing_an_operand_keeps_the_program_in_step() {
+        let mut data = program();
+        let instructions = instructions::<RawFuzzInstructionPrototype>(&data);
+
+        // The register operand takes the place of the integer.
+        let edit = Edit {
+            range: instructions[0].operands[0].range.clone(),
+            bytes: data[instructions[1].operands[0].range.clone()].to_vec(),
+        };
+        let size = edit.apply(&mut data, program().len());
+        assert_eq!(size, program().len());
+
+        let spliced = instructions::<RawFuzzInstructionPrototype>(&data[..size]);
+        assert_eq!(spliced.len(), 2);
+        assert_eq!(ranges(&spliced[0]), [9..17]);
+        assert_eq!(data[9..17], data[26..34]);
+    }
+}
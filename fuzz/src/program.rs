@@ -0,0 +1,86 @@
+//! The run `rawval_fuzz` and `typed_fuzz` make of a program, with every
+//! check they make of it, shared with the tools that replay their artifacts
+//! and with the regression tests those export, so all of them fail exactly
+//! where the targets do.
+
+use crate::budget::{assert_same_charges, BudgetOracle, Cost};
+use crate::fuzzcontract::{self, *};
+use crate::{panic_message, register_fuzz_contract, scratch, FuzzInstructionPrototype};
+use soroban_sdk::arbitrary::fuzz_catch_panic;
+use soroban_sdk::testutils::Logs;
+use soroban_sdk::{ConversionError, Env, Error, InvokeError, Val, Vec};
+
+/// What `try_run_program` returned.
+pub type ProgramResult = Result<Result<Vec<Val>, ConversionError>, Result<Error, InvokeError>>;
+
+/// Runs the program `instructions` builds as one call in `env`, then each
+/// of its instructions on its own, checking each call, and returns what the
+/// program returned and what each call charged.
+fn run(
+    env: &Env,
+    instructions: &impl Fn(&Env) -> Vec<FuzzInstruction>,
+    input_len: usize,
+) -> (ProgramResult, std::vec::Vec<Cost>) {
+    let contract_id = register_fuzz_contract(env);
+
+    let client = fuzzcontract::Client::new(env, &contract_id);
+    let mut budget = BudgetOracle::new(env, &client);
+
+    let program = FuzzProgram {
+        instructions: instructions(env),
+    };
+
+    // Returning an error is ok; panicking is not.
+    let panic_r = budget.call(env, || {
+        fuzz_catch_panic(|| client.try_run_program(&program))
+    });
+
+    let program_r = match panic_r {
+        Ok(program_r) => program_r,
+        Err(e) => {
+            if !env.logs().all().is_empty() {
+                env.logs().print();
+            }
+            panic!("host panicked: {}", panic_message(&*e));
+        }
+    };
+    if let Ok(Ok(results)) = &program_r {
+        scratch::check_program(env, &program, results);
+    }
+
+    budget.call_each(env, &client, &program);
+
+    (program_r, budget.finish(input_len))
+}
+
+/// Runs the program `instructions` builds the way `rawval_fuzz` and
+/// `typed_fuzz` run an input of `input_len` bytes: twice, each time in an
+/// `Env` from `new_env`. Returns what the program returned the first time,
+/// and panics where the targets would: if the host panics, or any check of
+/// the budget or of guest memory fails.
+pub fn check_instructions(
+    new_env: impl Fn() -> Env,
+    instructions: impl Fn(&Env) -> Vec<FuzzInstruction>,
+    input_len: usize,
+) -> ProgramResult {
+    let (program_r, charges) = run(&new_env(), &instructions, input_len);
+    assert_same_charges(&charges, &run(&new_env(), &instructions, input_len).1);
+    program_r
+}
+
+/// Runs a decoded input of `rawval_fuzz` or `typed_fuzz` the way the target
+/// does; see `check_instructions`.
+pub fn check(
+    new_env: impl Fn() -> Env,
+    input: &[FuzzInstructionPrototype],
+    input_len: usize,
+) -> ProgramResult {
+    let instructions = |env: &Env| {
+        let mut instructions = Vec::new(env);
+        for instr in input.iter() {
+            instructions.push_back(instr.to_guest(env));
+        }
+        instructions
+    };
+    check_instructions(new_env, instructions, input_len)
+}
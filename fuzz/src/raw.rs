@@ -0,0 +1,481 @@
+use crate::fuzzcontract::*;
+use crate::host_functions::{self, HostFunction};
+use crate::mutator::{Cursor, Instruction, OperandSpan, Operands};
+use crate::{FuzzPointerPrototype, FuzzStorageTypePrototype};
+use core::ops::{Range, RangeInclusive};
+use paste::paste;
+use soroban_env_common::{call_macro_with_all_host_functions, Tag};
+use soroban_sdk::arbitrary::arbitrary;
+use soroban_sdk::arbitrary::SorobanArbitrary;
+use soroban_sdk::{Env, FromVal, Val};
+
+/// An operand: a well-formed `Val`, a `Val` payload that may not be, or the
+/// result of an earlier instruction.
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum FuzzOperandPrototype {
+    Val(<Val as SorobanArbitrary>::Prototype),
+    RawVal(RawValPrototype),
+    Reg(u32),
+}
+
+impl FuzzOperandPrototype {
+    pub fn to_guest(&self, env: &Env) -> FuzzOperand {
+        match self {
+            FuzzOperandPrototype::Val(v) => {
+                let v = Val::from_val(env, v);
+                FuzzOperand::Val(FakeVal(v.get_payload()))
+            }
+            FuzzOperandPrototype::RawVal(v) => FuzzOperand::Val(FakeVal(v.payload())),
+            FuzzOperandPrototype::Reg(v) => FuzzOperand::Reg(*v),
+        }
+    }
+}
+
+const TAG_BITS: u32 = 8;
+const MINOR_MASK: u64 = (1 << 24) - 1;
+const SMALL_TAGS: Range<u8> = 0..Tag::SmallCodeUpperBound as u8;
+const OBJECT_TAGS: Range<u8> = Tag::ObjectCodeLowerBound as u8 + 1..Tag::ObjectCodeUpperBound as u8;
+/// The tags no `Val` has: the bounds of the small and object tags, those
+/// between them, and those past the last, `Tag::Bad` among them.
+const RESERVED_TAGS: [RangeInclusive<u8>; 2] = [
+    Tag::SmallCodeUpperBound as u8..=Tag::ObjectCodeLowerBound as u8,
+    Tag::ObjectCodeUpperBound as u8..=u8::MAX,
+];
+/// The tags whose `Val`s have no body, or a minor that must be zero.
+const ZERO_MINOR_TAGS: &[Tag] = &[Tag::False, Tag::True, Tag::Void, Tag::U32Val, Tag::I32Val];
+
+/// A `Val` payload built from a tag and a body, for the payloads that
+/// `SorobanArbitrary`, which only makes well-formed `Val`s, never does.
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum RawValPrototype {
+    /// A small `Val` of any tag, with any body.
+    Small { tag: u8, body: u64 },
+    /// An object of any type, whose handle may be that of an object of
+    /// another type, or of no object at all.
+    Object {
+        tag: u8,
+        handle: ObjectHandlePrototype,
+    },
+    /// A tag no `Val` has: one of the bounds of the small and object tags,
+    /// a tag between or past them, or `Tag::Bad`.
+    ReservedTag { tag: u8, body: u64 },
+    /// A tag whose `Val`s have no body or a zero minor, such as `Void` or
+    /// `U32Val` or any object, with a non-zero minor.
+    BadBody { tag: u8, body: u64 },
+    /// Any payload at all.
+    Payload(u64),
+}
+
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum ObjectHandlePrototype {
+    /// One of the first handles, which are likely of objects the host has
+    /// made, though of any type.
+    Low(u8),
+    /// Any handle, which is likely of no object.
+    Any(u32),
+}
+
+impl RawValPrototype {
+    pub fn payload(&self) -> u64 {
+        fn pick(tags: Range<u8>, i: u8) -> u8 {
+            tags.start + i % tags.len() as u8
+        }
+        let (tag, body) = match *self {
+            RawValPrototype::Small { tag, body } => (pick(SMALL_TAGS, tag), body),
+            RawValPrototype::Object { tag, ref handle } => {
+                let handle = match *handle {
+                    ObjectHandlePrototype::Low(h) => u32::from(h),
+                    ObjectHandlePrototype::Any(h) => h,
+                };
+                (pick(OBJECT_TAGS, tag), u64::from(handle) << 24)
+            }
+            RawValPrototype::ReservedTag { tag, body } => {
+                let reserved: std::vec::Vec<u8> =
+                    RESERVED_TAGS.iter().flat_map(|tags| tags.clone()).collect();
+                (reserved[usize::from(tag) % reserved.len()], body)
+            }
+            RawValPrototype::BadBody { tag, body } => {
+                let tags = ZERO_MINOR_TAGS.len() + OBJECT_TAGS.len();
+                let tag = match usize::from(tag) % tags {
+                    i if i < ZERO_MINOR_TAGS.len() => ZERO_MINOR_TAGS[i] as u8,
+                    i => OBJECT_TAGS.start + (i - ZERO_MINOR_TAGS.len()) as u8,
+                };
+                let body = if body & MINOR_MASK == 0 {
+                    body | 1
+                } else {
+                    body
+                };
+                (tag, body)
+            }
+            RawValPrototype::Payload(payload) => return payload,
+        };
+        body << TAG_BITS | u64::from(tag)
+    }
+}
+
+// This is a callback macro that pattern-matches the token-tree passed by the
+// x-macro (call_macro_with_all_host_functions) and produces
+// `RawFuzzInstructionPrototype` and one `RawModXPrototype` enum per host
+// module, mirroring the `RawFuzzInstruction` and `RawModX` enums the
+// contract generates from the same x-macro.
+macro_rules! generate_raw_prototypes {
+    {
+        $(
+            $(#[$mod_attr:meta])*
+            mod $mod_id:ident $mod_str:literal
+            {
+                $(
+                    $(#[$fn_attr:meta])*
+                    { $fn_str:literal, fn $fn_id:ident $args:tt -> $ret:ty }
+                )*
+            }
+        )*
+    }
+
+    =>
+
+    {
+        paste! {
+            #[derive(Clone, Debug, arbitrary::Arbitrary)]
+            pub enum RawFuzzInstructionPrototype {
+                $(
+                    [<$mod_id:camel>]([<RawMod $mod_id:camel Prototype>]),
+                )*
+            }
+
+            impl RawFuzzInstructionPrototype {
+                pub fn to_guest(&self, env: &Env) -> RawFuzzInstruction {
+                    match self {
+                        $(
+                            RawFuzzInstructionPrototype::[<$mod_id:camel>](v) => {
+                                RawFuzzInstruction::[<$mod_id:camel>](v.to_guest(env))
+                            }
+                        )*
+                    }
+                }
+
+                /// The host function this instruction calls.
+                pub fn host_function(&self) -> &'static HostFunction {
+                    match self {
+                        $(
+                            RawFuzzInstructionPrototype::[<$mod_id:camel>](v) => v.host_function(),
+                        )*
+                    }
+                }
+            }
+
+            impl Instruction for RawFuzzInstructionPrototype {
+                fn operand_spans(&self, cursor: &mut Cursor) -> std::vec::Vec<OperandSpan> {
+                    match self {
+                        $(
+                            RawFuzzInstructionPrototype::[<$mod_id:camel>](v) => {
+                                v.operand_spans(cursor)
+                            }
+                        )*
+                    }
+                }
+            }
+        }
+
+        $(
+            generate_raw_module_prototype! { env $mod_id [] [] [] [] $($fn_id $args)* }
+        )*
+    };
+}
+
+// This is a helper macro used only by generate_raw_prototypes above. It
+// consumes the functions of one module one at a time, accumulating the
+// variants of the module's prototype enum and the arms of its `to_guest`,
+// `operand_spans` and `host_function` methods, and emits them once every
+// function has been consumed.
+macro_rules! generate_raw_module_prototype {
+    {
+        $env:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*]
+    }
+    =>
+    {
+        paste! {
+            #[derive(Clone, Debug, arbitrary::Arbitrary)]
+            pub enum [<RawMod $mod_id:camel Prototype>] {
+                $($variant)*
+            }
+
+            impl [<RawMod $mod_id:camel Prototype>] {
+                #[allow(unused_variables)]
+                pub fn to_guest(&self, $env: &Env) -> [<RawMod $mod_id:camel>] {
+                    match self {
+                        $($arm)*
+                    }
+                }
+
+                /// Where the operands of this instruction lie, with `cursor`
+                /// at the first of them.
+                pub fn operand_spans(&self, cursor: &mut Cursor) -> std::vec::Vec<OperandSpan> {
+                    let spans: fn(&mut Cursor) -> std::vec::Vec<OperandSpan> = match self {
+                        $($span)*
+                    };
+                    spans(cursor)
+                }
+
+                /// The host function this instruction calls.
+                pub fn host_function(&self) -> &'static HostFunction {
+                    match self {
+                        $($host)*
+                    }
+                }
+            }
+        }
+    };
+
+    // These must match the shortened names in the contract's raw.rs.
+    {
+        $env:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*]
+        bump_current_contract_instance_and_code ($($args:tt)*) $($rest:tt)*
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env ($mod_id [$($variant)*] [$($arm)*] [$($span)*]
+                [$($host)* Self::BumpCurrentContract { .. } =>
+                    host_functions::named(stringify!($mod_id), "bump_current_contract_instance_and_code"),]
+                BumpCurrentContract [$($rest)*]) [] [] [] $($args)*
+        }
+    };
+
+    {
+        $env:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*]
+        $fn_id:ident ($($args:tt)*) $($rest:tt)*
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env ($mod_id [$($variant)*] [$($arm)*] [$($span)*]
+                [$($host)* Self::[<$fn_id:camel>] { .. } =>
+                    host_functions::named(stringify!($mod_id), stringify!($fn_id)),]
+                [<$fn_id:camel>] [$($rest)*]) [] [] [] $($args)*
+        }
+    };
+}
+
+// This is a helper macro used only by generate_raw_module_prototype above.
+// It consumes the argument list of one function one argument at a time,
+// accumulating the field types of the function's prototype variant, the
+// names its `to_guest` arm binds, and the expressions building the
+// contract's variant.
+macro_rules! generate_raw_args_prototype {
+    {
+        $env:ident ($mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*] $name:tt [$($rest:tt)*])
+        [] [] []
+    }
+    =>
+    {
+        generate_raw_module_prototype! {
+            $env $mod_id
+            [
+                $($variant)*
+                $name,
+            ]
+            [
+                $($arm)*
+                Self::$name => [<RawMod $mod_id:camel>]::$name,
+            ]
+            [
+                $($span)*
+                Self::$name => <() as Operands>::spans,
+            ]
+            [$($host)*]
+            $($rest)*
+        }
+    };
+
+    {
+        $env:ident ($mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*] $name:tt [$($rest:tt)*])
+        [$($field:tt)+] [$($bind:tt)+] [$($expr:tt)+]
+    }
+    =>
+    {
+        generate_raw_module_prototype! {
+            $env $mod_id
+            [
+                $($variant)*
+                $name($($field)+),
+            ]
+            [
+                $($arm)*
+                Self::$name($($bind)+) => [<RawMod $mod_id:camel>]::$name($($expr)+),
+            ]
+            [
+                $($span)*
+                Self::$name(..) => <($($field)+) as Operands>::spans,
+            ]
+            [$($host)*]
+            $($rest)*
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : StorageType $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env $func
+            [$($field)* FuzzStorageTypePrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_guest(),]
+            $($($args)*)?
+        }
+    };
+
+    // Arguments that point into guest linear memory, which the host
+    // functions name thus, are fuzzed as pointers into the scratch region
+    // rather than as plain integers. These arms mark them for the next one.
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        lm_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env $func [$($field)*] [$($bind)*] [$($expr)*] lm_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        msg_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env $func [$($field)*] [$($bind)*] [$($expr)*] msg_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        vals_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env $func [$($field)*] [$($bind)*] [$($expr)*] vals_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        keys_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env $func [$($field)*] [$($bind)*] [$($expr)*] keys_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        slices_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env $func [$($field)*] [$($bind)*] [$($expr)*] slices_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : LinearMemoryPos $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env $func
+            [$($field)* FuzzPointerPrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_guest($env),]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env $func
+            [$($field)* <u32 as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* *$arg,]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : u64 $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env $func
+            [$($field)* <u64 as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* *$arg,]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : i64 $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env $func
+            [$($field)* <i64 as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* *$arg,]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : $type:ident $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_raw_args_prototype! {
+            $env $func
+            [$($field)* FuzzOperandPrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_guest($env),]
+            $($($args)*)?
+        }
+    };
+}
+
+// Here we invoke the x-macro passing generate_raw_prototypes as its callback macro.
+call_macro_with_all_host_functions! { generate_raw_prototypes }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserved_tags_are_every_tag_no_val_has() {
+        let reserved: std::vec::Vec<u8> =
+            RESERVED_TAGS.iter().flat_map(|tags| tags.clone()).collect();
+        let valid = SMALL_TAGS.chain(OBJECT_TAGS);
+        assert!(reserved.contains(&(Tag::Bad as u8)));
+        assert!(valid.clone().all(|t| !reserved.contains(&t)));
+        assert_eq!(reserved.len() + valid.count(), 256);
+    }
+}
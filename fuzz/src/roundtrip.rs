@@ -0,0 +1,27 @@
+use crate::fuzzcontract::*;
+use crate::scval::ScValBytesPrototype;
+use soroban_sdk::arbitrary::arbitrary;
+use soroban_sdk::arbitrary::SorobanArbitrary;
+use soroban_sdk::{Bytes, Env, FromVal, Val};
+
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum FuzzRoundTripPrototype {
+    Serialize(<Val as SorobanArbitrary>::Prototype),
+    Deserialize(ScValBytesPrototype),
+    DeserializeRaw(<Bytes as SorobanArbitrary>::Prototype),
+}
+
+impl FuzzRoundTripPrototype {
+    pub fn to_guest(&self, env: &Env) -> FuzzRoundTrip {
+        match self {
+            FuzzRoundTripPrototype::Serialize(v) => {
+                let v = Val::from_val(env, v);
+                FuzzRoundTrip::Serialize(FakeVal(v.get_payload()))
+            }
+            FuzzRoundTripPrototype::Deserialize(b) => FuzzRoundTrip::Deserialize(b.to_guest(env)),
+            FuzzRoundTripPrototype::DeserializeRaw(b) => {
+                FuzzRoundTrip::DeserializeRaw(Bytes::from_val(env, b))
+            }
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! Checks of what the host functions that write to guest memory did to the
+//! contract's scratch region, as the `ScratchWrite`s those instructions
+//! return report it.
+//!
+//! The checks are made from the instructions as the contract receives them,
+//! so that a program rebuilt from its `Val`s, as the exported regression
+//! tests are, is checked the same as one built from prototypes.
+//!
+//! These only check anything against the contract's wasm build: natively
+//! the host refuses every call that touches guest memory. A call that fails
+//! traps the guest and discards its memory, so there is nothing to check
+//! after one.
+
+use crate::fuzzcontract::*;
+use contract_for_fuzz::scratch::{self, VAL_LEN};
+use soroban_env_common::SymbolStr;
+use soroban_sdk::{Env, Symbol, TryFromVal, Val, Vec};
+
+/// The length the contract passes a host function along with `pos` when
+/// the instruction gives `len`; see the contract's `scratch::fit`.
+fn fit(pos: &FuzzPointer, len: u32, unit: u32) -> usize {
+    match pos {
+        FuzzPointer::Scratch(offset, _) => scratch::fit_at(*offset, len, unit) as usize,
+        FuzzPointer::Wild(_) => len as usize,
+    }
+}
+
+/// The width of a `Val` in guest memory.
+const VAL_BYTES: usize = VAL_LEN as usize;
+
+/// Where an instruction asks its host function to write to guest memory,
+/// and what the host must write there, as far as the harness knows it.
+pub struct CopyOut {
+    pos: FuzzPointer,
+    len: usize,
+    /// Bytes the host must write, by their offset from `pos`.
+    expected: std::vec::Vec<(usize, std::vec::Vec<u8>)>,
+}
+
+impl CopyOut {
+    fn new(pos: &FuzzPointer, len: usize) -> CopyOut {
+        CopyOut {
+            pos: pos.clone(),
+            len,
+            expected: std::vec::Vec::new(),
+        }
+    }
+
+    fn expecting(mut self, offset: usize, bytes: std::vec::Vec<u8>) -> CopyOut {
+        self.expected.push((offset, bytes));
+        self
+    }
+
+    /// Checks that a successful call wrote exactly the range it was given,
+    /// and wrote there what the harness expects.
+    pub fn check(&self, write: &ScratchWrite) {
+        let before: std::vec::Vec<u8> = write.before.iter().collect();
+        let after: std::vec::Vec<u8> = write.after.iter().collect();
+        let start = match self.pos {
+            FuzzPointer::Scratch(offset, _) => i64::from(offset) % after.len() as i64,
+            FuzzPointer::Wild(pos) => i64::from(pos) - i64::from(write.base),
+        };
+        let range = start..start + self.len as i64;
+
+        for (i, (b, a)) in before.iter().zip(after.iter()).enumerate() {
+            if !range.contains(&(i as i64)) {
+                assert_eq!(
+                    b, a,
+                    "byte {i} of the scratch region, outside the range written, changed"
+                );
+            }
+        }
+        for (offset, bytes) in &self.expected {
+            for (i, b) in bytes.iter().enumerate() {
+                let at = start + (offset + i) as i64;
+                if let Some(a) = usize::try_from(at).ok().and_then(|at| after.get(at)) {
+                    assert_eq!(a, b, "byte {at} of the scratch region was written wrong");
+                }
+            }
+        }
+    }
+}
+
+/// An instruction whose host function may write to guest memory.
+pub trait CopyOutInstruction {
+    fn copy_out(&self, env: &Env) -> Option<CopyOut>;
+}
+
+impl CopyOutInstruction for FuzzInstruction {
+    fn copy_out(&self, env: &Env) -> Option<CopyOut> {
+        match self {
+            FuzzInstruction::Raw(instr) => instr.copy_out(env),
+            FuzzInstruction::Typed(instr) => instr.copy_out(env),
+            _ => None,
+        }
+    }
+}
+
+impl CopyOutInstruction for RawFuzzInstruction {
+    fn copy_out(&self, _env: &Env) -> Option<CopyOut> {
+        let (pos, len) = match self {
+            RawFuzzInstruction::Buf(
+                RawModBuf::BytesCopyToLinearMemory(_, _, pos, len)
+                | RawModBuf::StringCopyToLinearMemory(_, _, pos, len)
+                | RawModBuf::SymbolCopyToLinearMemory(_, _, pos, len),
+            ) => (pos, fit(pos, *len, 1)),
+            RawFuzzInstruction::Vec(RawModVec::VecUnpackToLinearMemory(_, pos, len))
+            | RawFuzzInstruction::Map(RawModMap::MapUnpackToLinearMemory(_, _, pos, len)) => {
+                (pos, fit(pos, *len, VAL_LEN) * VAL_BYTES)
+            }
+            _ => return None,
+        };
+        Some(CopyOut::new(pos, len))
+    }
+}
+
+impl CopyOutInstruction for TypedFuzzInstruction {
+    fn copy_out(&self, env: &Env) -> Option<CopyOut> {
+        let slice = |bytes: std::vec::Vec<u8>, from: u32, len: usize| {
+            let from = from as usize;
+            bytes.get(from..from + len).unwrap_or_default().to_vec()
+        };
+        let copy_out = match self {
+            // The harness doesn't know what a register holds, so the
+            // contents copied out of an object from one aren't checked.
+            TypedFuzzInstruction::Buf(TypedModBuf::BytesCopyToLinearMemory(b, b_pos, pos, len)) => {
+                let len = fit(pos, *len, 1);
+                let copy_out = CopyOut::new(pos, len);
+                let BytesOperand::Val(b) = b else {
+                    return Some(copy_out);
+                };
+                copy_out.expecting(0, slice(b.iter().collect(), *b_pos, len))
+            }
+            TypedFuzzInstruction::Buf(TypedModBuf::StringCopyToLinearMemory(
+                s,
+                s_pos,
+                pos,
+                len,
+            )) => {
+                let len = fit(pos, *len, 1);
+                let copy_out = CopyOut::new(pos, len);
+                let StringOperand::Val(s) = s else {
+                    return Some(copy_out);
+                };
+                let mut bytes = vec![0; s.len() as usize];
+                s.copy_into_slice(&mut bytes);
+                copy_out.expecting(0, slice(bytes, *s_pos, len))
+            }
+            TypedFuzzInstruction::Buf(TypedModBuf::SymbolCopyToLinearMemory(
+                s,
+                s_pos,
+                pos,
+                len,
+            )) => {
+                let len = fit(pos, *len, 1);
+                let bytes = SymbolStr::try_from_val(env, &s.to_symbol_val())
+                    .map(|s| AsRef::<[u8]>::as_ref(&s).to_vec())
+                    .unwrap_or_default();
+                CopyOut::new(pos, len).expecting(0, slice(bytes, *s_pos, len))
+            }
+            // Objects are written as handles the harness cannot predict, so
+            // only the other elements are checked.
+            TypedFuzzInstruction::Vec(TypedModVec::VecUnpackToLinearMemory(v, pos, len)) => {
+                let len = fit(pos, *len, VAL_LEN);
+                let mut copy_out = CopyOut::new(pos, len * VAL_BYTES);
+                let VecOperand::Val(v) = v else {
+                    return Some(copy_out);
+                };
+                for (i, e) in v.iter().enumerate().filter(|(_, e)| !e.is_object()) {
+                    let payload = e.get_payload().to_le_bytes().to_vec();
+                    copy_out = copy_out.expecting(i * VAL_BYTES, payload);
+                }
+                copy_out
+            }
+            // The values are written in the order of keys the host reads
+            // from guest memory, so only the range is checked.
+            TypedFuzzInstruction::Map(TypedModMap::MapUnpackToLinearMemory(_, _, pos, len)) => {
+                CopyOut::new(pos, fit(pos, *len, VAL_LEN) * VAL_BYTES)
+            }
+            // `call` returns what the instruction it passes to another copy
+            // of the contract returned. `try_call` is not checked, since it
+            // returns an error instead when that copy fails.
+            TypedFuzzInstruction::Call(TypedModCall::Call(_, func, VecOperand::Val(args)))
+                if *func == Symbol::new(env, "run") && args.len() == 1 =>
+            {
+                let instr = FuzzInstruction::try_from_val(env, &args.get_unchecked(0)).ok()?;
+                return instr.copy_out(env);
+            }
+            _ => return None,
+        };
+        Some(copy_out)
+    }
+}
+
+/// Checks the `ScratchWrite`s of a program that ran to completion.
+pub fn check_program(env: &Env, program: &FuzzProgram, results: &Vec<Val>) {
+    for (instr, result) in program.instructions.iter().zip(results.iter()) {
+        if let Some(copy_out) = instr.copy_out(env) {
+            let write = ScratchWrite::try_from_val(env, &result)
+                .expect("a host function that writes to guest memory returns a ScratchWrite");
+            copy_out.check(&write);
+        }
+    }
+}
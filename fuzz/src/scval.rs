@@ -0,0 +1,380 @@
+//! XDR encodings of `ScVal`s, for the bytes `DeserializeFromBytes` parses.
+//!
+//! Arbitrary bytes almost never parse as an `ScVal`: the deserializer rejects
+//! them at the first tag or length it reads. These are instead encoded from
+//! a generated value, nested vecs and maps included, and then corrupted in
+//! at most one controlled way, so the depth limit and the error paths past
+//! the first tag get exercised.
+
+use core::ops::Range;
+use soroban_sdk::arbitrary::arbitrary;
+use soroban_sdk::xdr::{
+    ContractExecutableType, PublicKeyType, ScAddressType, ScErrorCode, ScErrorType, ScValType,
+    SCSYMBOL_LIMIT,
+};
+use soroban_sdk::{Bytes, Env};
+
+/// The deepest `Nest` goes, which is past the host's XDR depth limit of
+/// 500.
+const MAX_NEST: u16 = 600;
+
+/// One node of a value, in pre-order: a vec, map or contract instance is
+/// followed by the nodes of its elements. Elements past the last node are
+/// `Void`.
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum ScValNode {
+    Bool(bool),
+    Void,
+    /// An `ScErrorType`, and a contract's code or an `ScErrorCode`, both
+    /// wrapped to the valid ones.
+    Error(u8, u32),
+    U32(u32),
+    I32(i32),
+    U64(u64),
+    I64(i64),
+    Timepoint(u64),
+    Duration(u64),
+    U128(u128),
+    I128(i128),
+    U256([u64; 4]),
+    I256([u64; 4]),
+    Bytes(std::vec::Vec<u8>),
+    String(std::vec::Vec<u8>),
+    /// Its bytes are mapped to the characters symbols may have.
+    Symbol(std::vec::Vec<u8>),
+    /// The number of elements, or none for an absent vec.
+    Vec(Option<u8>),
+    /// The number of entries, or none for an absent map.
+    Map(Option<u8>),
+    Address(ScAddressNode),
+    /// The hash of its wasm, or none for a Stellar asset, and the number of
+    /// entries of its storage, or none for no storage.
+    ContractInstance(Option<[u8; 32]>, Option<u8>),
+    LedgerKeyContractInstance,
+    LedgerKeyNonce(i64),
+}
+
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum ScAddressNode {
+    Account([u8; 32]),
+    Contract([u8; 32]),
+}
+
+/// How to corrupt an encoding. The `u8`s pick one of the places of that
+/// kind, wrapped to their number; if there are none the encoding is left
+/// alone.
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum ScValCorruption {
+    None,
+    /// Cuts this many bytes off the end.
+    Truncate(u16),
+    /// Replaces a length or an element count.
+    Count(u8, u32),
+    /// Replaces a union discriminant, an enum or a bool.
+    Discriminant(u8, u32),
+    /// Wraps the value in this many vecs of one element.
+    Nest(u16),
+    /// Fills the padding after opaque bytes or a string with this byte.
+    Padding(u8, u8),
+}
+
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub struct ScValBytesPrototype {
+    pub nodes: std::vec::Vec<ScValNode>,
+    pub corruption: ScValCorruption,
+}
+
+impl ScValBytesPrototype {
+    /// The encoding of the value, corrupted.
+    pub fn to_xdr(&self) -> std::vec::Vec<u8> {
+        let mut enc = Encoder {
+            nodes: self.nodes.iter(),
+            out: std::vec::Vec::new(),
+            counts: std::vec::Vec::new(),
+            discriminants: std::vec::Vec::new(),
+            padding: std::vec::Vec::new(),
+        };
+        if let ScValCorruption::Nest(depth) = self.corruption {
+            for _ in 0..depth.min(MAX_NEST) {
+                enc.discriminant(ScValType::Vec as u32);
+                enc.discriminant(1);
+                enc.count(1);
+            }
+        }
+        enc.value();
+
+        let pick = |places: &[usize], i: u8| match places.len() {
+            0 => None,
+            n => Some(places[i as usize % n]),
+        };
+        let mut out = enc.out;
+        match self.corruption {
+            ScValCorruption::None | ScValCorruption::Nest(_) => {}
+            ScValCorruption::Truncate(n) => out.truncate(out.len().saturating_sub(n as usize)),
+            ScValCorruption::Count(i, count) => {
+                if let Some(at) = pick(&enc.counts, i) {
+                    out[at..at + 4].copy_from_slice(&count.to_be_bytes());
+                }
+            }
+            ScValCorruption::Discriminant(i, d) => {
+                if let Some(at) = pick(&enc.discriminants, i) {
+                    out[at..at + 4].copy_from_slice(&d.to_be_bytes());
+                }
+            }
+            ScValCorruption::Padding(i, b) => {
+                if !enc.padding.is_empty() {
+                    let range = enc.padding[i as usize % enc.padding.len()].clone();
+                    out[range].fill(b);
+                }
+            }
+        }
+        out
+    }
+
+    pub fn to_guest(&self, env: &Env) -> Bytes {
+        Bytes::from_slice(env, &self.to_xdr())
+    }
+}
+
+/// Encodes the nodes of a value, noting where the places `ScValCorruption`
+/// may corrupt lie.
+struct Encoder<'a> {
+    nodes: core::slice::Iter<'a, ScValNode>,
+    out: std::vec::Vec<u8>,
+    counts: std::vec::Vec<usize>,
+    discriminants: std::vec::Vec<usize>,
+    padding: std::vec::Vec<Range<usize>>,
+}
+
+impl Encoder<'_> {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.out.extend_from_slice(bytes);
+    }
+
+    fn count(&mut self, count: u32) {
+        self.counts.push(self.out.len());
+        self.bytes(&count.to_be_bytes());
+    }
+
+    fn discriminant(&mut self, d: u32) {
+        self.discriminants.push(self.out.len());
+        self.bytes(&d.to_be_bytes());
+    }
+
+    /// Variable-length opaque bytes or a string: the length, the bytes, and
+    /// zeroes up to a multiple of four.
+    fn opaque(&mut self, bytes: &[u8]) {
+        self.count(bytes.len() as u32);
+        self.bytes(bytes);
+        let start = self.out.len();
+        self.out.resize(start + (4 - bytes.len() % 4) % 4, 0);
+        if self.out.len() > start {
+            self.padding.push(start..self.out.len());
+        }
+    }
+
+    /// An optional vec or map, with `elements` values per entry.
+    fn elements(&mut self, count: Option<u8>, elements: usize) {
+        match count {
+            None => self.discriminant(0),
+            Some(count) => {
+                self.discriminant(1);
+                self.count(count as u32);
+                for _ in 0..count as usize * elements {
+                    self.value();
+                }
+            }
+        }
+    }
+
+    fn value(&mut self) {
+        let Some(node) = self.nodes.next() else {
+            self.discriminant(ScValType::Void as u32);
+            return;
+        };
+        match node {
+            ScValNode::Bool(b) => {
+                self.discriminant(ScValType::Bool as u32);
+                self.discriminant(*b as u32);
+            }
+            ScValNode::Void => self.discriminant(ScValType::Void as u32),
+            ScValNode::Error(t, code) => {
+                self.discriminant(ScValType::Error as u32);
+                let t = ScErrorType::VARIANTS[usize::from(*t) % ScErrorType::VARIANTS.len()];
+                self.discriminant(t as u32);
+                if t == ScErrorType::Contract {
+                    self.bytes(&code.to_be_bytes());
+                } else {
+                    let codes = ScErrorCode::VARIANTS;
+                    self.discriminant(codes[*code as usize % codes.len()] as u32);
+                }
+            }
+            ScValNode::U32(v) => {
+                self.discriminant(ScValType::U32 as u32);
+                self.bytes(&v.to_be_bytes());
+            }
+            ScValNode::I32(v) => {
+                self.discriminant(ScValType::I32 as u32);
+                self.bytes(&v.to_be_bytes());
+            }
+            ScValNode::U64(v) => {
+                self.discriminant(ScValType::U64 as u32);
+                self.bytes(&v.to_be_bytes());
+            }
+            ScValNode::I64(v) => {
+                self.discriminant(ScValType::I64 as u32);
+                self.bytes(&v.to_be_bytes());
+            }
+            ScValNode::Timepoint(v) => {
+                self.discriminant(ScValType::Timepoint as u32);
+                self.bytes(&v.to_be_bytes());
+            }
+            ScValNode::Duration(v) => {
+                self.discriminant(ScValType::Duration as u32);
+                self.bytes(&v.to_be_bytes());
+            }
+            // The 128-bit integers are their high and then their low halves,
+            // as are their big-endian bytes.
+            ScValNode::U128(v) => {
+                self.discriminant(ScValType::U128 as u32);
+                self.bytes(&v.to_be_bytes());
+            }
+            ScValNode::I128(v) => {
+                self.discriminant(ScValType::I128 as u32);
+                self.bytes(&v.to_be_bytes());
+            }
+            ScValNode::U256(parts) | ScValNode::I256(parts) => {
+                let t = match node {
+                    ScValNode::U256(_) => ScValType::U256,
+                    _ => ScValType::I256,
+                };
+                self.discriminant(t as u32);
+                for part in parts {
+                    self.bytes(&part.to_be_bytes());
+                }
+            }
+            ScValNode::Bytes(b) => {
+                self.discriminant(ScValType::Bytes as u32);
+                self.opaque(b);
+            }
+            ScValNode::String(s) => {
+                self.discriminant(ScValType::String as u32);
+                self.opaque(s);
+            }
+            ScValNode::Symbol(s) => {
+                self.discriminant(ScValType::Symbol as u32);
+                let s: std::vec::Vec<u8> = s
+                    .iter()
+                    .take(SCSYMBOL_LIMIT as usize)
+                    .map(|b| match b {
+                        b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' => *b,
+                        _ => b'_',
+                    })
+                    .collect();
+                self.opaque(&s);
+            }
+            ScValNode::Vec(count) => {
+                self.discriminant(ScValType::Vec as u32);
+                self.elements(*count, 1);
+            }
+            ScValNode::Map(count) => {
+                self.discriminant(ScValType::Map as u32);
+                self.elements(*count, 2);
+            }
+            ScValNode::Address(address) => {
+                self.discriminant(ScValType::Address as u32);
+                match address {
+                    ScAddressNode::Account(key) => {
+                        self.discriminant(ScAddressType::Account as u32);
+                        self.discriminant(PublicKeyType::PublicKeyTypeEd25519 as u32);
+                        self.bytes(key);
+                    }
+                    ScAddressNode::Contract(hash) => {
+                        self.discriminant(ScAddressType::Contract as u32);
+                        self.bytes(hash);
+                    }
+                }
+            }
+            ScValNode::ContractInstance(wasm, storage) => {
+                self.discriminant(ScValType::ContractInstance as u32);
+                match wasm {
+                    Some(hash) => {
+                        self.discriminant(ContractExecutableType::Wasm as u32);
+                        self.bytes(hash);
+                    }
+                    None => self.discriminant(ContractExecutableType::StellarAsset as u32),
+                }
+                self.elements(*storage, 2);
+            }
+            ScValNode::LedgerKeyContractInstance => {
+                self.discriminant(ScValType::LedgerKeyContractInstance as u32);
+            }
+            ScValNode::LedgerKeyNonce(nonce) => {
+                self.discriminant(ScValType::LedgerKeyNonce as u32);
+                self.bytes(&nonce.to_be_bytes());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::xdr::{ReadXdr, ScBytes, ScMap, ScMapEntry, ScSymbol, ScVal, ScVec};
+
+    fn decode(nodes: std::vec::Vec<ScValNode>) -> ScVal {
+        let xdr = ScValBytesPrototype {
+            nodes,
+            corruption: ScValCorruption::None,
+        }
+        .to_xdr();
+        ScVal::from_xdr(&xdr).unwrap()
+    }
+
+    #[test]
+    fn uncorrupted_encodings_parse_back() {
+        assert_eq!(decode(vec![]), ScVal::Void);
+        assert_eq!(decode(vec![ScValNode::U32(7)]), ScVal::U32(7));
+
+        let vec = ScVec(
+            vec![
+                ScVal::I64(-2),
+                ScVal::Symbol(ScSymbol("a_b".try_into().unwrap())),
+            ]
+            .try_into()
+            .unwrap(),
+        );
+        assert_eq!(
+            decode(vec![
+                ScValNode::Vec(Some(2)),
+                ScValNode::I64(-2),
+                ScValNode::Symbol(b"a-b".to_vec()),
+            ]),
+            ScVal::Vec(Some(vec))
+        );
+
+        // Elements past the last node are `Void`.
+        let vec = ScVec(vec![ScVal::U32(1), ScVal::Void].try_into().unwrap());
+        assert_eq!(
+            decode(vec![ScValNode::Vec(Some(2)), ScValNode::U32(1)]),
+            ScVal::Vec(Some(vec))
+        );
+
+        let map = ScMap(
+            vec![ScMapEntry {
+                key: ScVal::Bool(true),
+                val: ScVal::Bytes(ScBytes(vec![1, 2, 3].try_into().unwrap())),
+            }]
+            .try_into()
+            .unwrap(),
+        );
+        assert_eq!(
+            decode(vec![
+                ScValNode::Map(Some(1)),
+                ScValNode::Bool(true),
+                ScValNode::Bytes(vec![1, 2, 3]),
+            ]),
+            ScVal::Map(Some(map))
+        );
+    }
+}
@@ -0,0 +1,651 @@
+use crate::fuzzcontract::*;
+use crate::host_functions::{self, HostFunction};
+use crate::mutator::{Cursor, Instruction, OperandSpan, Operands};
+use crate::scval::ScValBytesPrototype;
+use crate::{FuzzCallPrototype, FuzzPointerPrototype, FuzzStorageTypePrototype};
+use paste::paste;
+use soroban_env_common::call_macro_with_all_host_functions;
+use soroban_sdk::arbitrary::arbitrary;
+use soroban_sdk::arbitrary::SorobanArbitrary;
+use soroban_sdk::{Address, Bytes, Env, FromVal, Map, String, Symbol, Val, Vec};
+
+/// A `Val` argument of a typed instruction: a value, or a register holding
+/// the result of an earlier instruction.
+#[derive(Clone, Debug, arbitrary::Arbitrary)]
+pub enum ValOperandPrototype {
+    Val(<Val as SorobanArbitrary>::Prototype),
+    Reg(u32),
+}
+
+impl ValOperandPrototype {
+    pub fn to_guest(&self, env: &Env) -> FuzzOperand {
+        match self {
+            ValOperandPrototype::Val(v) => {
+                FuzzOperand::Val(FakeVal(Val::from_val(env, v).get_payload()))
+            }
+            ValOperandPrototype::Reg(i) => FuzzOperand::Reg(*i),
+        }
+    }
+}
+
+// Produces the prototype of an object argument of the typed instructions,
+// mirroring the operand enum the contract has for it.
+macro_rules! typed_object_operand_prototype {
+    ($name:ident($sdk_type:ty) -> $operand:ident) => {
+        #[derive(Clone, Debug, arbitrary::Arbitrary)]
+        pub enum $name {
+            Val(<$sdk_type as SorobanArbitrary>::Prototype),
+            Reg(u32),
+        }
+
+        impl $name {
+            pub fn to_guest(&self, env: &Env) -> $operand {
+                match self {
+                    $name::Val(v) => $operand::Val(<$sdk_type>::from_val(env, v)),
+                    $name::Reg(i) => $operand::Reg(*i),
+                }
+            }
+        }
+    };
+}
+
+typed_object_operand_prototype!(AddressOperandPrototype(Address) -> AddressOperand);
+typed_object_operand_prototype!(BytesOperandPrototype(Bytes) -> BytesOperand);
+typed_object_operand_prototype!(MapOperandPrototype(Map<Val, Val>) -> MapOperand);
+typed_object_operand_prototype!(StringOperandPrototype(String) -> StringOperand);
+typed_object_operand_prototype!(VecOperandPrototype(Vec<Val>) -> VecOperand);
+
+// This is a callback macro that pattern-matches the token-tree passed by the
+// x-macro (call_macro_with_all_host_functions) and produces
+// `TypedFuzzInstructionPrototype` and one `TypedModXPrototype` enum per host
+// module, mirroring the `TypedFuzzInstruction` and `TypedModX` enums the
+// contract generates from the same x-macro.
+macro_rules! generate_typed_prototypes {
+    {
+        $(
+            $(#[$mod_attr:meta])*
+            mod $mod_id:ident $mod_str:literal
+            {
+                $(
+                    $(#[$fn_attr:meta])*
+                    { $fn_str:literal, fn $fn_id:ident $args:tt -> $ret:ty }
+                )*
+            }
+        )*
+    }
+
+    =>
+
+    {
+        paste! {
+            #[derive(Clone, Debug, arbitrary::Arbitrary)]
+            pub enum TypedFuzzInstructionPrototype {
+                $(
+                    [<$mod_id:camel>]([<TypedMod $mod_id:camel Prototype>]),
+                )*
+            }
+
+            impl TypedFuzzInstructionPrototype {
+                pub fn to_guest(&self, env: &Env) -> TypedFuzzInstruction {
+                    match self {
+                        $(
+                            TypedFuzzInstructionPrototype::[<$mod_id:camel>](v) => {
+                                TypedFuzzInstruction::[<$mod_id:camel>](v.to_guest(env))
+                            }
+                        )*
+                    }
+                }
+
+                /// The host function this instruction calls.
+                pub fn host_function(&self) -> &'static HostFunction {
+                    match self {
+                        $(
+                            TypedFuzzInstructionPrototype::[<$mod_id:camel>](v) => v.host_function(),
+                        )*
+                    }
+                }
+            }
+
+            impl Instruction for TypedFuzzInstructionPrototype {
+                fn operand_spans(&self, cursor: &mut Cursor) -> std::vec::Vec<OperandSpan> {
+                    match self {
+                        $(
+                            TypedFuzzInstructionPrototype::[<$mod_id:camel>](v) => {
+                                v.operand_spans(cursor)
+                            }
+                        )*
+                    }
+                }
+            }
+        }
+
+        $(
+            generate_typed_module_prototype! { env $mod_id [] [] [] [] $($fn_id $args)* }
+        )*
+    };
+}
+
+// This is a helper macro used only by generate_typed_prototypes above. It
+// consumes the functions of one module one at a time, accumulating the
+// variants of the module's prototype enum and the arms of its `to_guest`,
+// `operand_spans` and `host_function` methods, and emits them once every
+// function has been consumed.
+macro_rules! generate_typed_module_prototype {
+    {
+        $env:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*]
+    }
+    =>
+    {
+        paste! {
+            #[derive(Clone, Debug, arbitrary::Arbitrary)]
+            pub enum [<TypedMod $mod_id:camel Prototype>] {
+                $($variant)*
+            }
+
+            impl [<TypedMod $mod_id:camel Prototype>] {
+                #[allow(unused_variables)]
+                pub fn to_guest(&self, $env: &Env) -> [<TypedMod $mod_id:camel>] {
+                    match self {
+                        $($arm)*
+                    }
+                }
+
+                /// Where the operands of this instruction lie, with `cursor`
+                /// at the first of them.
+                pub fn operand_spans(&self, cursor: &mut Cursor) -> std::vec::Vec<OperandSpan> {
+                    let spans: fn(&mut Cursor) -> std::vec::Vec<OperandSpan> = match self {
+                        $($span)*
+                    };
+                    spans(cursor)
+                }
+
+                /// The host function this instruction calls.
+                pub fn host_function(&self) -> &'static HostFunction {
+                    match self {
+                        $($host)*
+                    }
+                }
+            }
+        }
+    };
+
+    // These must match the shortened names in the contract's typed.rs.
+    {
+        $env:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*]
+        bump_current_contract_instance_and_code ($($args:tt)*) $($rest:tt)*
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env ($mod_id [$($variant)*] [$($arm)*] [$($span)*]
+                [$($host)* Self::BumpCurrentContract { .. } =>
+                    host_functions::named(stringify!($mod_id), "bump_current_contract_instance_and_code"),]
+                BumpCurrentContract [$($rest)*]) [] [] [] $($args)*
+        }
+    };
+
+    // Calls are fuzzed mostly as calls into the other copies of the fuzz
+    // contract, since an arbitrary address is almost never a contract.
+    {
+        $env:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*]
+        call (contract : AddressObject, func : Symbol, args : VecObject) $($rest:tt)*
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env ($mod_id [$($variant)*] [$($arm)*] [$($span)*]
+                [$($host)* Self::Call { .. } =>
+                    host_functions::named(stringify!($mod_id), "call"),]
+                Call [$($rest)*]) [] [] [] call : FuzzCall
+        }
+    };
+
+    {
+        $env:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*]
+        try_call (contract : AddressObject, func : Symbol, args : VecObject) $($rest:tt)*
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env ($mod_id [$($variant)*] [$($arm)*] [$($span)*]
+                [$($host)* Self::TryCall { .. } =>
+                    host_functions::named(stringify!($mod_id), "try_call"),]
+                TryCall [$($rest)*]) [] [] [] call : FuzzCall
+        }
+    };
+
+    // The bytes `deserialize_from_bytes` parses are generated as XDR
+    // `ScVal`s, which arbitrary bytes almost never are.
+    {
+        $env:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*]
+        deserialize_from_bytes ($arg:ident : BytesObject) $($rest:tt)*
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env ($mod_id [$($variant)*] [$($arm)*] [$($span)*]
+                [$($host)* Self::DeserializeFromBytes { .. } =>
+                    host_functions::named(stringify!($mod_id), "deserialize_from_bytes"),]
+                DeserializeFromBytes [$($rest)*]) [] [] [] $arg : ScValBytes
+        }
+    };
+
+    {
+        $env:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*]
+        $fn_id:ident ($($args:tt)*) $($rest:tt)*
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env ($mod_id [$($variant)*] [$($arm)*] [$($span)*]
+                [$($host)* Self::[<$fn_id:camel>] { .. } =>
+                    host_functions::named(stringify!($mod_id), stringify!($fn_id)),]
+                [<$fn_id:camel>] [$($rest)*]) [] [] [] $($args)*
+        }
+    };
+}
+
+// This is a helper macro used only by generate_typed_module_prototype above.
+// It consumes the argument list of one function one argument at a time,
+// accumulating the field types of the function's prototype variant, the
+// names its `to_guest` arm binds, and the expressions building the
+// contract's variant. The argument types follow the contract's typed.rs.
+macro_rules! generate_typed_args_prototype {
+    {
+        $env:ident ($mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*] $name:tt [$($rest:tt)*])
+        [] [] []
+    }
+    =>
+    {
+        generate_typed_module_prototype! {
+            $env $mod_id
+            [
+                $($variant)*
+                $name,
+            ]
+            [
+                $($arm)*
+                Self::$name => [<TypedMod $mod_id:camel>]::$name,
+            ]
+            [
+                $($span)*
+                Self::$name => <() as Operands>::spans,
+            ]
+            [$($host)*]
+            $($rest)*
+        }
+    };
+
+    {
+        $env:ident ($mod_id:ident [$($variant:tt)*] [$($arm:tt)*] [$($span:tt)*] [$($host:tt)*] $name:tt [$($rest:tt)*])
+        [$($field:tt)+] [$($bind:tt)+] [$($expr:tt)+]
+    }
+    =>
+    {
+        generate_typed_module_prototype! {
+            $env $mod_id
+            [
+                $($variant)*
+                $name($($field)+),
+            ]
+            [
+                $($arm)*
+                Self::$name($($bind)+) => [<TypedMod $mod_id:camel>]::$name($($expr)+),
+            ]
+            [
+                $($span)*
+                Self::$name(..) => <($($field)+) as Operands>::spans,
+            ]
+            [$($host)*]
+            $($rest)*
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : StorageType $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* FuzzStorageTypePrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_guest(),]
+            $($($args)*)?
+        }
+    };
+
+    // Arguments that point into guest linear memory, which the host
+    // functions name thus, are fuzzed as pointers into the scratch region
+    // rather than as plain integers. These arms mark them for the next one.
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        lm_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func [$($field)*] [$($bind)*] [$($expr)*] lm_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        msg_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func [$($field)*] [$($bind)*] [$($expr)*] msg_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        vals_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func [$($field)*] [$($bind)*] [$($expr)*] vals_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        keys_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func [$($field)*] [$($bind)*] [$($expr)*] keys_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        slices_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func [$($field)*] [$($bind)*] [$($expr)*] slices_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : LinearMemoryPos $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* FuzzPointerPrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_guest($env),]
+            $($($args)*)?
+        }
+    };
+
+    // One prototype stands for all three arguments of a call.
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : FuzzCall $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* FuzzCallPrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* AddressOperand::Val($arg.contract($env)), $arg.func($env), VecOperand::Val($arg.args($env)),]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : ScValBytes $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* ScValBytesPrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* BytesOperand::Val($arg.to_guest($env)),]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* <u32 as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* *$arg,]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : u64 $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* <u64 as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* *$arg,]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : i64 $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* <i64 as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* *$arg,]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : AddressObject $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* AddressOperandPrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_guest($env),]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : BytesObject $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* BytesOperandPrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_guest($env),]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : MapObject $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* MapOperandPrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_guest($env),]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : StringObject $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* StringOperandPrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_guest($env),]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : VecObject $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* VecOperandPrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_guest($env),]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : Symbol $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* <Symbol as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* Symbol::from_val($env, $arg),]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : SymbolObject $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* <Symbol as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* Symbol::from_val($env, $arg),]
+            $($($args)*)?
+        }
+    };
+
+    // Integer objects are fuzzed as the integers they hold.
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : I64Object $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* <i64 as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* *$arg,]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : U64Object $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* <u64 as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* *$arg,]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : I128Object $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* <i128 as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* *$arg,]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : U128Object $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* <u128 as SorobanArbitrary>::Prototype,]
+            [$($bind)* $arg,]
+            [$($expr)* *$arg,]
+            $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : $type:ident $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args_prototype! {
+            $env $func
+            [$($field)* ValOperandPrototype,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_guest($env),]
+            $($($args)*)?
+        }
+    };
+}
+
+// Here we invoke the x-macro passing generate_typed_prototypes as its callback macro.
+call_macro_with_all_host_functions! { generate_typed_prototypes }
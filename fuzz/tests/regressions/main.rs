@@ -0,0 +1,51 @@
+//! Crashes found by the fuzz targets, kept as regression tests.
+//!
+//! Each module below is written by the `export_regression` tool and holds one
+//! crashing program. The tests run it the way the target that found it does,
+//! against the contract's wasm build and with every check the target makes,
+//! so build that first:
+//!
+//! ```text
+//! cargo build --target wasm32-unknown-unknown --release
+//! cd fuzz && cargo test --test regressions
+//! ```
+
+// Until the first regression is exported, nothing uses these.
+#![allow(dead_code)]
+
+use contract_for_fuzz_fuzz::fuzzcontract::FuzzInstruction;
+use contract_for_fuzz_fuzz::program;
+use soroban_sdk::xdr::ScVal;
+use soroban_sdk::{Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+
+/// Converts a value the exported programs spell out into a `Val`.
+pub fn val(env: &Env, v: impl IntoVal<Env, Val>) -> Val {
+    v.into_val(env)
+}
+
+/// A symbol, which is also how a variant of a contract type is named.
+pub fn sym(env: &Env, s: &str) -> Val {
+    Symbol::new(env, s).to_val()
+}
+
+/// A value the SDK has no type of its own for, given as XDR.
+pub fn scval(env: &Env, v: ScVal) -> Val {
+    Val::try_from_val(env, &v).unwrap()
+}
+
+/// Runs a program, built by `instructions` as the `Val`s of its
+/// `FuzzInstruction`s, the way `rawval_fuzz` and `typed_fuzz` run an input
+/// of `input_len` bytes, and panics where they would.
+pub fn check_program(input_len: usize, instructions: impl Fn(&Env) -> Vec<Val>) {
+    program::check_instructions(
+        Env::default,
+        |env| {
+            let mut program = Vec::new(env);
+            for instr in instructions(env).iter() {
+                program.push_back(FuzzInstruction::try_from_val(env, &instr).unwrap());
+            }
+            program
+        },
+        input_len,
+    );
+}
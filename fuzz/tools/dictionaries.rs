@@ -0,0 +1,173 @@
+//! Writes libFuzzer dictionaries for the fuzz targets into
+//! `dicts/<target>.dict`.
+//!
+//! The entries are the magic bytes that host functions check before doing
+//! anything interesting, taken from the env's own definitions: the `ScValType`
+//! tags `DeserializeFromBytes` parses, the characters and lengths symbols
+//! may have, the wasm header and sections `UploadWasm` parses along with the
+//! env meta section it requires, and `Val` payloads of every `Tag`.
+//!
+//! ```text
+//! cd fuzz && cargo run --bin dictionaries
+//! cargo fuzz run typed_fuzz -- -dict=dicts/typed_fuzz.dict
+//! ```
+//!
+//! Byte strings reach the host inside `Bytes` operands, which `arbitrary`
+//! reads with a flag byte before each byte, so each byte string is written
+//! both as is and in that form.
+
+use soroban_env_common::meta::{ENV_META_V0_SECTION_NAME, INTERFACE_VERSION};
+use soroban_env_common::{SymbolSmall, Tag};
+use soroban_sdk::xdr::{ScValType, SCSYMBOL_LIMIT};
+use std::fmt::Write;
+use std::path::Path;
+use std::process::ExitCode;
+
+const DICTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/dicts");
+
+/// The targets whose inputs hold host function operands, which get every
+/// entry.
+const INSTRUCTION_TARGETS: &[&str] = &[
+    "rawval_fuzz",
+    "typed_fuzz",
+    "diff_fuzz",
+    "budget_fuzz",
+    "replay_fuzz",
+    "roundtrip_fuzz",
+];
+
+/// The targets whose inputs hold only the `u64`s and `u32`s of collection
+/// operations, which get only the `Val` payloads.
+const COLLECTION_TARGETS: &[&str] = &["vec_fuzz", "map_fuzz"];
+
+/// The wasm binary magic and version 1.
+const WASM_HEADER: &[u8] = b"\0asm\x01\0\0\0";
+/// The sections of the wasm spec, by name and id, with the data count
+/// section of the bulk memory proposal, which the spec has since taken in.
+const WASM_SECTIONS: &[(&str, u8)] = &[
+    ("custom", 0),
+    ("type", 1),
+    ("import", 2),
+    ("function", 3),
+    ("table", 4),
+    ("memory", 5),
+    ("global", 6),
+    ("export", 7),
+    ("start", 8),
+    ("element", 9),
+    ("code", 10),
+    ("data", 11),
+    ("data_count", 12),
+];
+
+struct Dict(String);
+
+impl Dict {
+    fn entry(&mut self, name: &str, value: &[u8]) {
+        let escaped: String = value.iter().map(|b| format!("\\x{b:02x}")).collect();
+        writeln!(self.0, "{name}=\"{escaped}\"").unwrap();
+    }
+
+    /// Adds a byte string as is, and as the contents of a `Bytes` operand.
+    fn byte_string(&mut self, name: &str, value: &[u8]) {
+        self.entry(name, value);
+        let operand: std::vec::Vec<u8> = value.iter().flat_map(|b| [1, *b]).collect();
+        self.entry(&format!("{name}_bytes"), &operand);
+    }
+
+    fn section(&mut self, comment: &str) {
+        if !self.0.is_empty() {
+            self.0.push('\n');
+        }
+        writeln!(self.0, "# {comment}").unwrap();
+    }
+}
+
+fn xdr_entries(dict: &mut Dict) {
+    dict.section("ScValType tags, as XDR");
+    for t in ScValType::VARIANTS {
+        let name = format!("scval_{}", t.name().to_lowercase());
+        dict.byte_string(&name, &(t as i32).to_be_bytes());
+    }
+}
+
+fn symbol_entries(dict: &mut Dict) {
+    let chars: std::vec::Vec<u8> = (0..=u8::MAX)
+        .filter(|b| SymbolSmall::try_from_bytes(&[*b]).is_ok())
+        .collect();
+    let max_small = (1..=SCSYMBOL_LIMIT as usize)
+        .take_while(|len| SymbolSmall::try_from_bytes(&vec![b'a'; *len]).is_ok())
+        .last()
+        .unwrap_or(0);
+
+    dict.section("Symbol characters, and symbols of the longest lengths");
+    dict.byte_string("symbol_chars", &chars);
+    for (name, len) in [
+        ("symbol_max_small", max_small),
+        ("symbol_min_object", max_small + 1),
+        ("symbol_max", SCSYMBOL_LIMIT as usize),
+    ] {
+        let symbol: std::vec::Vec<u8> = chars.iter().copied().cycle().take(len).collect();
+        dict.byte_string(name, &symbol);
+    }
+}
+
+fn wasm_entries(dict: &mut Dict) {
+    dict.section("Wasm header, section ids, and the env meta section");
+    dict.byte_string("wasm_header", WASM_HEADER);
+    for (name, id) in WASM_SECTIONS {
+        dict.byte_string(&format!("wasm_section_{name}"), &[*id]);
+    }
+    dict.byte_string("env_meta_section_name", ENV_META_V0_SECTION_NAME.as_bytes());
+    // An `ScEnvMetaEntry::ScEnvMetaKindInterfaceVersion`, as XDR.
+    let mut meta = 0u32.to_be_bytes().to_vec();
+    meta.extend_from_slice(&INTERFACE_VERSION.to_be_bytes());
+    dict.byte_string("env_meta_interface_version", &meta);
+}
+
+fn val_entries(dict: &mut Dict) {
+    dict.section("Val payloads of every tag, with a zero body and with a major of 1");
+    for t in 0..=u8::MAX {
+        let tag = Tag::from_u8(t);
+        if tag as u8 != t {
+            continue;
+        }
+        let name = format!("val_{tag:?}").to_lowercase();
+        dict.entry(&name, &u64::from(t).to_le_bytes());
+        dict.entry(
+            &format!("{name}_1"),
+            &(1 << 32 | u64::from(t)).to_le_bytes(),
+        );
+    }
+}
+
+fn main() -> ExitCode {
+    let mut instruction_dict = Dict(String::new());
+    xdr_entries(&mut instruction_dict);
+    symbol_entries(&mut instruction_dict);
+    wasm_entries(&mut instruction_dict);
+    val_entries(&mut instruction_dict);
+
+    let mut collection_dict = Dict(String::new());
+    val_entries(&mut collection_dict);
+
+    let dir = Path::new(DICTS_DIR);
+    let written = std::fs::create_dir_all(dir).and_then(|()| {
+        for (targets, dict) in [
+            (INSTRUCTION_TARGETS, &instruction_dict),
+            (COLLECTION_TARGETS, &collection_dict),
+        ] {
+            for target in targets {
+                std::fs::write(dir.join(format!("{target}.dict")), &dict.0)?;
+            }
+        }
+        Ok(())
+    });
+    if let Err(e) = written {
+        eprintln!("{}: {e}", dir.display());
+        return ExitCode::FAILURE;
+    }
+    println!("wrote dictionaries to {}", dir.display());
+
+    ExitCode::SUCCESS
+}
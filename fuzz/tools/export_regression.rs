@@ -0,0 +1,353 @@
+//! Exports a crash found by `rawval_fuzz` or `typed_fuzz` as a regression
+//! test of the fuzz crate.
+//!
+//! Decodes the artifact the way `repro` does and writes the program the
+//! target ran into a `#[test]` in `tests/regressions/`, adding the new module
+//! to the `main.rs` there. The test spells each instruction out as the `Val`
+//! the contract receives, below a comment with the instruction as decoded,
+//! and runs the program with every check the target makes, so it fails on a
+//! failed check of the budget or of guest memory as well as on a host panic.
+//!
+//! ```text
+//! cd fuzz && cargo run --bin export_regression -- typed_fuzz artifacts/typed_fuzz/crash-...
+//! ```
+//!
+//! The test rebuilds the program from those values rather than from the
+//! prototypes, so an operand holding an object handle may name a different
+//! object than it did in the fuzzer. The program is run that way first, and
+//! a crash that does not reproduce like that is not exported.
+
+use contract_for_fuzz_fuzz::fuzzcontract::*;
+use contract_for_fuzz_fuzz::{decode_program, panic_message, program};
+use soroban_sdk::arbitrary::fuzz_catch_panic;
+use soroban_sdk::xdr::{AccountId, PublicKey, ScAddress, ScErrorCode, ScErrorType, ScVal, Uint256};
+use soroban_sdk::{Env, Error, IntoVal, TryFromVal, Val, Vec};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::io::Write as _;
+use std::path::Path;
+use std::process::{Command, ExitCode, Stdio};
+
+const REGRESSIONS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/regressions");
+
+fn artifact_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Names the test after the target and the artifact, which libFuzzer names
+/// after a hash of its contents.
+fn test_name(target: &str, path: &Path) -> String {
+    let artifact: String = artifact_name(path).chars().take(18).collect();
+    format!("{target}_{artifact}")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Spells `Val`s out as the expressions of the exported test, keeping track
+/// of the names those use.
+struct Spelling<'a> {
+    env: &'a Env,
+    sdk: BTreeSet<&'static str>,
+    xdr: BTreeSet<&'static str>,
+    helpers: BTreeSet<&'static str>,
+}
+
+impl<'a> Spelling<'a> {
+    fn new(env: &'a Env) -> Spelling<'a> {
+        Spelling {
+            env,
+            sdk: BTreeSet::new(),
+            xdr: BTreeSet::new(),
+            helpers: BTreeSet::new(),
+        }
+    }
+
+    fn val(&mut self, v: impl std::fmt::Display) -> String {
+        self.helpers.insert("val");
+        format!("val(env, {v})")
+    }
+
+    fn bytes(&mut self, ty: &'static str, bytes: &[u8]) -> String {
+        self.sdk.insert(ty);
+        self.helpers.insert("val");
+        let ctor = if ty == "Bytes" {
+            "from_slice"
+        } else {
+            "from_bytes"
+        };
+        self.val(format!("{ty}::{ctor}(env, &{bytes:?})"))
+    }
+
+    /// A value the SDK has no type of its own for, as its XDR.
+    fn scval(&mut self, names: &[&'static str], scval: String) -> String {
+        self.helpers.insert("scval");
+        self.xdr.insert("ScVal");
+        self.xdr.extend(names);
+        format!("scval(env, {scval})")
+    }
+
+    fn error(&mut self, v: &ScVal) -> String {
+        let e = Error::try_from_val(self.env, &Val::try_from_val(self.env, v).unwrap()).unwrap();
+        self.sdk.insert("Error");
+        if e.is_type(ScErrorType::Contract) {
+            return self.val(format!("Error::from_contract_error({})", e.get_code()));
+        }
+        let type_ = ScErrorType::VARIANTS.into_iter().find(|t| e.is_type(*t));
+        let code = ScErrorCode::VARIANTS.into_iter().find(|c| e.is_code(*c));
+        self.xdr.extend(["ScErrorType", "ScErrorCode"]);
+        self.val(format!(
+            "Error::from_type_and_code(ScErrorType::{}, ScErrorCode::{})",
+            type_.unwrap().name(),
+            code.unwrap().name()
+        ))
+    }
+
+    fn address(&mut self, a: &ScAddress) -> String {
+        let address = match a {
+            ScAddress::Contract(hash) => {
+                self.xdr.insert("Hash");
+                format!("ScAddress::Contract(Hash({:?}))", hash.0)
+            }
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key)))) => {
+                self.xdr.extend(["AccountId", "PublicKey", "Uint256"]);
+                format!("ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256({key:?}))))")
+            }
+        };
+        self.scval(&["ScAddress"], format!("ScVal::Address({address})"))
+    }
+
+    fn expr(&mut self, v: &ScVal) -> String {
+        match v {
+            ScVal::Bool(b) => self.val(b),
+            ScVal::Void => self.val("()"),
+            ScVal::Error(_) => self.error(v),
+            ScVal::U32(n) => self.val(format!("{n}u32")),
+            ScVal::I32(n) => self.val(format!("{n}i32")),
+            ScVal::U64(n) => self.val(format!("{n}u64")),
+            ScVal::I64(n) => self.val(format!("{n}i64")),
+            ScVal::Timepoint(t) => self.scval(&["TimePoint"], format!("ScVal::Timepoint({t:?})")),
+            ScVal::Duration(d) => self.scval(&["Duration"], format!("ScVal::Duration({d:?})")),
+            ScVal::U128(parts) => self.val(format!(
+                "{}u128",
+                u128::from(parts.hi) << 64 | u128::from(parts.lo)
+            )),
+            ScVal::I128(parts) => self.val(format!(
+                "{}i128",
+                i128::from(parts.hi) << 64 | i128::from(parts.lo)
+            )),
+            ScVal::U256(parts) => self.scval(&["UInt256Parts"], format!("ScVal::U256({parts:?})")),
+            ScVal::I256(parts) => self.scval(&["Int256Parts"], format!("ScVal::I256({parts:?})")),
+            ScVal::Bytes(b) => self.bytes("Bytes", b.0.as_slice()),
+            ScVal::String(s) => self.bytes("String", s.0.as_slice()),
+            ScVal::Symbol(s) => {
+                self.helpers.insert("sym");
+                let s = std::string::String::from_utf8_lossy(s.0.as_slice());
+                format!("sym(env, \"{s}\")")
+            }
+            ScVal::Vec(Some(v)) => {
+                self.helpers.insert("val");
+                if v.is_empty() {
+                    self.sdk.insert("Vec");
+                    return self.val("Vec::<Val>::new(env)");
+                }
+                self.sdk.insert("vec");
+                let elems: std::vec::Vec<String> = v.iter().map(|e| self.expr(e)).collect();
+                self.val(format!("vec![env, {}]", elems.join(", ")))
+            }
+            ScVal::Map(Some(m)) => {
+                self.helpers.insert("val");
+                if m.is_empty() {
+                    self.sdk.insert("Map");
+                    return self.val("Map::<Val, Val>::new(env)");
+                }
+                self.sdk.insert("map");
+                let entries: std::vec::Vec<String> = m
+                    .iter()
+                    .map(|e| format!("({}, {})", self.expr(&e.key), self.expr(&e.val)))
+                    .collect();
+                self.val(format!("map![env, {}]", entries.join(", ")))
+            }
+            ScVal::Address(a) => self.address(a),
+            _ => panic!("{v:?} cannot be held in a Val"),
+        }
+    }
+
+    /// The `use` declarations for the names the expressions spelled so far
+    /// use.
+    fn uses(&self) -> String {
+        let mut src = String::new();
+        for (path, names) in [
+            ("super", &self.helpers),
+            ("soroban_sdk::xdr", &self.xdr),
+            ("soroban_sdk", &self.sdk),
+        ] {
+            if !names.is_empty() {
+                let names: std::vec::Vec<_> = names.iter().copied().collect();
+                writeln!(src, "use {path}::{{{}}};", names.join(", ")).unwrap();
+            }
+        }
+        src
+    }
+}
+
+fn test_source(
+    target: &str,
+    path: &Path,
+    name: &str,
+    input_len: usize,
+    program: &[FuzzInstruction],
+    scvals: &[ScVal],
+) -> String {
+    let env = Env::default();
+    let mut spelling = Spelling::new(&env);
+    spelling.sdk.insert("vec");
+    let instructions: std::vec::Vec<String> = scvals.iter().map(|v| spelling.expr(v)).collect();
+
+    let mut src = String::new();
+    writeln!(
+        src,
+        "//! Exported by `export_regression` from the `{target}` artifact"
+    )
+    .unwrap();
+    writeln!(src, "//! `{}`.", artifact_name(path)).unwrap();
+    writeln!(src).unwrap();
+    write!(src, "{}", spelling.uses()).unwrap();
+    writeln!(src).unwrap();
+    writeln!(src, "#[test]").unwrap();
+    writeln!(src, "fn {name}() {{").unwrap();
+    writeln!(src, "    super::check_program({input_len}, |env| {{").unwrap();
+    writeln!(src, "        vec![").unwrap();
+    writeln!(src, "            env,").unwrap();
+    for (i, (instr, expr)) in program.iter().zip(&instructions).enumerate() {
+        writeln!(src, "            // Instruction {i}:").unwrap();
+        for line in format!("{instr:#?}").lines() {
+            writeln!(src, "            // {line}").unwrap();
+        }
+        writeln!(src, "            {expr},").unwrap();
+    }
+    writeln!(src, "        ]").unwrap();
+    writeln!(src, "    }});").unwrap();
+    writeln!(src, "}}").unwrap();
+    src
+}
+
+/// Lays the test out with rustfmt, which breaks up the expressions the
+/// program is spelled out in, or returns `None` if that fails.
+fn rustfmt(src: &str) -> Option<String> {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .ok()?;
+    child.stdin.take()?.write_all(src.as_bytes()).ok()?;
+    let output = child.wait_with_output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8(output.stdout).ok())
+        .flatten()
+}
+
+/// Writes the test module and adds it to `main.rs`, unless it is already
+/// there. The modules are kept in order at the end of `main.rs`, as rustfmt
+/// would sort them.
+fn write_test(name: &str, src: &str) -> std::io::Result<()> {
+    let dir = Path::new(REGRESSIONS_DIR);
+    std::fs::write(dir.join(format!("{name}.rs")), src)?;
+
+    let main_path = dir.join("main.rs");
+    let main = std::fs::read_to_string(&main_path)?;
+    let mod_line = format!("mod {name};");
+    let (head, mods): (std::vec::Vec<&str>, std::vec::Vec<&str>) =
+        main.lines().partition(|line| !line.starts_with("mod "));
+    if !mods.contains(&mod_line.as_str()) {
+        let mut mods = mods;
+        mods.push(&mod_line);
+        mods.sort();
+        let head = head.join("\n");
+        let sep = if head.ends_with('}') { "\n\n" } else { "\n" };
+        std::fs::write(&main_path, format!("{head}{sep}{}\n", mods.join("\n")))?;
+    }
+    Ok(())
+}
+
+/// Runs the program the way the exported test will, rebuilt in a fresh
+/// `Env` from the values the test spells out and with every check the
+/// target makes, and returns the message it crashed with, if it did.
+fn reproduces(scvals: &[ScVal], input_len: usize) -> Option<String> {
+    let instructions = |env: &Env| {
+        let mut instructions = Vec::new(env);
+        for v in scvals {
+            let v = Val::try_from_val(env, v).unwrap();
+            instructions.push_back(FuzzInstruction::try_from_val(env, &v).unwrap());
+        }
+        instructions
+    };
+    fuzz_catch_panic(|| {
+        program::check_instructions(Env::default, instructions, input_len);
+    })
+    .err()
+    .map(|e| panic_message(&*e).to_string())
+}
+
+fn main() -> ExitCode {
+    let args: std::vec::Vec<String> = std::env::args().collect();
+    let [_, target, path] = args.as_slice() else {
+        eprintln!("usage: export_regression <rawval_fuzz|typed_fuzz> <artifact>");
+        return ExitCode::FAILURE;
+    };
+    let path = Path::new(path);
+
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("{}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let input = match decode_program(target, &data) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let env = Env::default();
+    let program: std::vec::Vec<FuzzInstruction> =
+        input.iter().map(|instr| instr.to_guest(&env)).collect();
+    let scvals: std::vec::Vec<ScVal> = program
+        .iter()
+        .map(|instr| {
+            let v: Val = instr.clone().into_val(&env);
+            ScVal::try_from_val(&env, &v).unwrap()
+        })
+        .collect();
+
+    let Some(msg) = reproduces(&scvals, data.len()) else {
+        eprintln!(
+            "{}: the program does not crash when rebuilt from its values; not exported",
+            path.display()
+        );
+        return ExitCode::SUCCESS;
+    };
+    println!("crashed: {}", msg.lines().next().unwrap_or_default());
+
+    let name = test_name(target, path);
+    let mut src = test_source(target, path, &name, data.len(), &program, &scvals);
+    match rustfmt(&src) {
+        Some(formatted) => src = formatted,
+        None => eprintln!("warning: rustfmt failed; the exported test is not formatted"),
+    }
+    if let Err(e) = write_test(&name, &src) {
+        eprintln!("{REGRESSIONS_DIR}: {e}");
+        return ExitCode::FAILURE;
+    }
+    println!("exported {name}");
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,205 @@
+//! Checks that every host function in the env interface is reachable through
+//! the contract's raw and typed instructions.
+//!
+//! Reads the contract spec embedded in `contract_for_fuzz.wasm` and compares
+//! its `RawModX` and `TypedModX` unions against the host functions listed by
+//! `call_macro_with_all_host_functions`. Host functions with no instruction,
+//! instructions with no host function, duplicated variants, and variants whose
+//! fields don't match the host function's arguments are reported, and any
+//! report makes the check fail.
+//!
+//! Run it after building the contract:
+//!
+//! ```text
+//! cargo build --target wasm32-unknown-unknown --release
+//! cd fuzz && cargo run --bin host_fn_coverage
+//! ```
+
+use contract_for_fuzz_fuzz::host_functions::{
+    camel_case, HostFunction, HOST_FUNCTIONS, LINEAR_MEMORY_ARGS,
+};
+use soroban_sdk::xdr::{ScSpecEntry, ScSpecTypeDef, ScSpecUdtUnionCaseV0};
+use std::collections::BTreeMap;
+use std::process::ExitCode;
+
+const WASM: &[u8] =
+    include_bytes!("../../target/wasm32-unknown-unknown/release/contract_for_fuzz.wasm");
+
+/// The field type raw.rs generates for a host function argument, given its
+/// name and type.
+fn raw_field((arg_name, arg_type): (&str, &str)) -> &'static str {
+    if LINEAR_MEMORY_ARGS.contains(&arg_name) {
+        return "FuzzPointer";
+    }
+    match arg_type {
+        "StorageType" => "FuzzStorageType",
+        "U32Val" => "u32",
+        "u64" => "u64",
+        "i64" => "i64",
+        _ => "FuzzOperand",
+    }
+}
+
+/// The field type typed.rs generates for a host function argument, given its
+/// name and type.
+fn typed_field((arg_name, arg_type): (&str, &str)) -> &'static str {
+    if LINEAR_MEMORY_ARGS.contains(&arg_name) {
+        return "FuzzPointer";
+    }
+    match arg_type {
+        "StorageType" => "FuzzStorageType",
+        "U32Val" => "u32",
+        "u64" | "U64Object" => "u64",
+        "i64" | "I64Object" => "i64",
+        "U128Object" => "u128",
+        "I128Object" => "i128",
+        "AddressObject" => "AddressOperand",
+        "BytesObject" => "BytesOperand",
+        "MapObject" => "MapOperand",
+        "StringObject" => "StringOperand",
+        "VecObject" => "VecOperand",
+        "Symbol" | "SymbolObject" => "Symbol",
+        _ => "FuzzOperand",
+    }
+}
+
+fn spec_type_name(t: &ScSpecTypeDef) -> String {
+    match t {
+        ScSpecTypeDef::Val => "Val".to_string(),
+        ScSpecTypeDef::U32 => "u32".to_string(),
+        ScSpecTypeDef::I32 => "i32".to_string(),
+        ScSpecTypeDef::U64 => "u64".to_string(),
+        ScSpecTypeDef::I64 => "i64".to_string(),
+        ScSpecTypeDef::U128 => "u128".to_string(),
+        ScSpecTypeDef::I128 => "i128".to_string(),
+        ScSpecTypeDef::Bytes => "Bytes".to_string(),
+        ScSpecTypeDef::String => "String".to_string(),
+        ScSpecTypeDef::Symbol => "Symbol".to_string(),
+        ScSpecTypeDef::Address => "Address".to_string(),
+        ScSpecTypeDef::Vec(v) => format!("Vec<{}>", spec_type_name(&v.element_type)),
+        ScSpecTypeDef::Map(m) => format!(
+            "Map<{}, {}>",
+            spec_type_name(&m.key_type),
+            spec_type_name(&m.value_type)
+        ),
+        ScSpecTypeDef::Udt(u) => u.name.to_string(),
+        t => format!("{t:?}"),
+    }
+}
+
+/// Reads the cases of every union in the contract spec, keyed by union name,
+/// as variant names and field types in declaration order.
+fn read_unions() -> BTreeMap<String, Vec<(String, Vec<String>)>> {
+    let entries = soroban_spec::read::from_wasm(WASM).expect("contract spec");
+    let mut unions = BTreeMap::new();
+    for entry in entries {
+        if let ScSpecEntry::UdtUnionV0(union) = entry {
+            let cases = union
+                .cases
+                .iter()
+                .map(|case| match case {
+                    ScSpecUdtUnionCaseV0::VoidV0(v) => (v.name.to_string(), Vec::new()),
+                    ScSpecUdtUnionCaseV0::TupleV0(t) => (
+                        t.name.to_string(),
+                        t.type_.iter().map(spec_type_name).collect(),
+                    ),
+                })
+                .collect();
+            unions.insert(union.name.to_string(), cases);
+        }
+    }
+    unions
+}
+
+/// Compares one instruction family against the host functions, returning a
+/// line per problem found.
+fn check_instructions(
+    unions: &BTreeMap<String, Vec<(String, Vec<String>)>>,
+    prefix: &str,
+    field: fn((&str, &str)) -> &'static str,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let mut modules: BTreeMap<&str, Vec<&HostFunction>> = BTreeMap::new();
+    for host_fn in HOST_FUNCTIONS {
+        modules.entry(host_fn.module).or_default().push(host_fn);
+    }
+
+    let top_name = format!("{prefix}FuzzInstruction");
+    let Some(top) = unions.get(&top_name) else {
+        return vec![format!("missing: {top_name}")];
+    };
+    for (module, host_fns) in &modules {
+        let module_variant = camel_case(module);
+        let enum_name = format!("{prefix}Mod{module_variant}");
+        if !top.iter().any(|(name, _)| *name == module_variant) {
+            problems.push(format!("missing: {top_name}::{module_variant} ({module})"));
+        }
+        let Some(cases) = unions.get(&enum_name) else {
+            problems.push(format!("missing: {enum_name} ({module})"));
+            continue;
+        };
+
+        for host_fn in host_fns {
+            let name = host_fn.variant_name();
+            let expected: Vec<&str> = host_fn.args.iter().map(|arg| field(*arg)).collect();
+            let matching: Vec<_> = cases.iter().filter(|(case, _)| *case == name).collect();
+            match matching.as_slice() {
+                [] => problems.push(format!(
+                    "missing: {enum_name}::{name} ({module}::{})",
+                    host_fn.name
+                )),
+                [(_, fields)] if *fields != expected => problems.push(format!(
+                    "mis-mapped: {enum_name}::{name} has fields ({}), {module}::{} needs ({})",
+                    fields.join(", "),
+                    host_fn.name,
+                    expected.join(", ")
+                )),
+                [_] => {}
+                _ => problems.push(format!("duplicated: {enum_name}::{name}")),
+            }
+        }
+
+        for (case, _) in cases {
+            if !host_fns
+                .iter()
+                .any(|host_fn| host_fn.variant_name() == *case)
+            {
+                problems.push(format!("no host function: {enum_name}::{case}"));
+            }
+        }
+    }
+
+    for (module_variant, _) in top {
+        if !modules
+            .keys()
+            .any(|module| camel_case(module) == *module_variant)
+        {
+            problems.push(format!("no host module: {top_name}::{module_variant}"));
+        }
+    }
+
+    problems
+}
+
+fn main() -> ExitCode {
+    let unions = read_unions();
+
+    let mut problems = check_instructions(&unions, "Raw", raw_field);
+    problems.extend(check_instructions(&unions, "Typed", typed_field));
+
+    println!(
+        "{} host functions, {} problems",
+        HOST_FUNCTIONS.len(),
+        problems.len()
+    );
+    for problem in &problems {
+        println!("  {problem}");
+    }
+
+    if problems.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
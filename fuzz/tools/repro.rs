@@ -0,0 +1,87 @@
+//! Reproduces a crash found by `rawval_fuzz` or `typed_fuzz`.
+//!
+//! Decodes the artifact through the same prototypes the target does, prints
+//! the program it ran, instruction by instruction, both as `FuzzInstruction`s
+//! and as the `ScVal`s the contract receives, and runs it again the way the
+//! target does, with every check the target makes, then once more with
+//! every diagnostic event enabled.
+//!
+//! ```text
+//! cd fuzz && cargo run --bin repro -- typed_fuzz artifacts/typed_fuzz/crash-...
+//! ```
+//!
+//! It exits with failure if the host panics again or a check fails, so it
+//! can also be used to check whether a crash still reproduces.
+
+use contract_for_fuzz_fuzz::{decode_program, panic_message, program};
+use soroban_env_host::DiagnosticLevel;
+use soroban_sdk::arbitrary::fuzz_catch_panic;
+use soroban_sdk::testutils::Logs;
+use soroban_sdk::xdr::ScVal;
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val};
+use std::cell::RefCell;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: std::vec::Vec<String> = std::env::args().collect();
+    let [_, target, path] = args.as_slice() else {
+        eprintln!("usage: repro <rawval_fuzz|typed_fuzz> <artifact>");
+        return ExitCode::FAILURE;
+    };
+
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("{path}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let input = match decode_program(target, &data) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{path}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let env = Env::default();
+    for (i, instr) in input.iter().enumerate() {
+        let fuzz_instruction = instr.to_guest(&env);
+        let val: Val = fuzz_instruction.clone().into_val(&env);
+        let scval = ScVal::try_from_val(&env, &val).unwrap();
+        println!("instruction {i}: {fuzz_instruction:#?}");
+        println!("instruction {i} as ScVal: {scval:?}");
+    }
+
+    // Run just as the target runs it, since recording diagnostic events may
+    // charge the budget differently.
+    let panic_r = fuzz_catch_panic(|| program::check(Env::default, &input, data.len()));
+
+    // Then again with every diagnostic event enabled, for the events. The
+    // target runs the program in more than one `Env`; those of the first are
+    // printed.
+    let envs = RefCell::new(std::vec::Vec::new());
+    let new_env = || {
+        let env = Env::default();
+        env.host()
+            .set_diagnostic_level(DiagnosticLevel::Debug)
+            .unwrap();
+        envs.borrow_mut().push(env.clone());
+        env
+    };
+    let _ = fuzz_catch_panic(|| program::check(new_env, &input, data.len()));
+    if let Some(env) = envs.borrow().first() {
+        env.logs().print();
+    }
+
+    match panic_r {
+        Ok(program_r) => {
+            println!("returned: {program_r:?}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("crashed: {}", panic_message(&*e));
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,772 @@
+//! Writes seed corpora for the fuzz targets into `corpus/<target>/`, where
+//! `cargo fuzz run` picks them up.
+//!
+//! Every host function of `RawModX` and `TypedModX` gets a few seeds of its
+//! own in each target that runs instructions: one with every operand left at
+//! its default and some with random operands. Typed host functions that
+//! random bytes hardly ever call successfully also get crafted seeds: valid
+//! ed25519 signatures and secp256k1 recoveries, a valid contract wasm, and
+//! map operations on keys the map holds. `vec_fuzz` and `map_fuzz` get, for
+//! each operation and mode, a collection and ten operations on elements it
+//! holds. `roundtrip_fuzz` gets each kind of round trip of a few values.
+//!
+//! ```text
+//! cd fuzz && cargo run --bin seed_corpus -- [target...]
+//! ```
+//!
+//! The seeds are written in the byte format `arbitrary` decodes, with the
+//! discriminants of the generated prototype enums, and the operands of the
+//! `vec_fuzz` and `map_fuzz` operations, found by decoding rather than
+//! hard-coded. Each seed is decoded again before it is written, and
+//! each crafted seed is also run, so a seed that does not mean what it was
+//! built to is reported and left out.
+
+use contract_for_fuzz_fuzz::collections::{map, vec, Mode};
+use contract_for_fuzz_fuzz::fuzzcontract::{self, *};
+use contract_for_fuzz_fuzz::roundtrip::FuzzRoundTripPrototype;
+use contract_for_fuzz_fuzz::scval::{ScValBytesPrototype, ScValCorruption, ScValNode};
+use contract_for_fuzz_fuzz::typed::{
+    BytesOperandPrototype, MapOperandPrototype, TypedFuzzInstructionPrototype, ValOperandPrototype,
+};
+use contract_for_fuzz_fuzz::{
+    decode_program, register_fuzz_contract, FuzzInstructionPrototype, FuzzPointerPrototype,
+};
+use soroban_env_common::meta::{ENV_META_V0_SECTION_NAME, INTERFACE_VERSION};
+use soroban_sdk::arbitrary::arbitrary::{Arbitrary, Unstructured};
+use soroban_sdk::arbitrary::{fuzz_catch_panic, SorobanArbitrary};
+use soroban_sdk::{Bytes, Env, FromVal, IntoVal, Map, Val, Vec};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::path::Path;
+use std::process::ExitCode;
+
+const CORPUS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/corpus");
+
+const TARGETS: &[&str] = &[
+    "rawval_fuzz",
+    "typed_fuzz",
+    "diff_fuzz",
+    "budget_fuzz",
+    "replay_fuzz",
+    "vec_fuzz",
+    "map_fuzz",
+    "roundtrip_fuzz",
+];
+
+/// Seeds with random operands per host function, besides the default one.
+const RANDOM_SEEDS: usize = 3;
+/// Attempts at random operands that decode to exactly one instruction.
+const RANDOM_ATTEMPTS: usize = 32;
+
+/// RFC 8032, section 7.1, test 2.
+const ED25519_PUBLIC_KEY: [u8; 32] = [
+    0x3d, 0x40, 0x17, 0xc3, 0xe8, 0x43, 0x89, 0x5a, 0x92, 0xb7, 0x0a, 0xa7, 0x4d, 0x1b, 0x7e, 0xbc,
+    0x9c, 0x98, 0x2c, 0xcf, 0x2e, 0xc4, 0x96, 0x8c, 0xc0, 0xcd, 0x55, 0xf1, 0x2a, 0xf4, 0x66, 0x0c,
+];
+const ED25519_MESSAGE: [u8; 1] = [0x72];
+const ED25519_SIGNATURE: [u8; 64] = [
+    0x92, 0xa0, 0x09, 0xa9, 0xf0, 0xd4, 0xca, 0xb8, 0x72, 0x0e, 0x82, 0x0b, 0x5f, 0x64, 0x25, 0x40,
+    0xa2, 0xb2, 0x7b, 0x54, 0x16, 0x50, 0x3f, 0x8f, 0xb3, 0x76, 0x22, 0x23, 0xeb, 0xdb, 0x69, 0xda,
+    0x08, 0x5a, 0xc1, 0xe4, 0x3e, 0x15, 0x99, 0x6e, 0x45, 0x8f, 0x36, 0x13, 0xd0, 0xf1, 0x1d, 0x8c,
+    0x38, 0x7b, 0x2e, 0xae, 0xb4, 0x30, 0x2a, 0xee, 0xb0, 0x0d, 0x29, 0x16, 0x12, 0xbb, 0x0c, 0x00,
+];
+
+/// The SHA-256 digest of `b"contract-for-fuzz"`, signed with a low-s
+/// signature by the secp256k1 key whose secret is the SHA-256 digest of
+/// `b"contract-for-fuzz secp256k1 seed"`.
+const SECP256K1_DIGEST: [u8; 32] = [
+    0x1a, 0x7c, 0xd2, 0x58, 0x27, 0x20, 0x65, 0x3e, 0x5e, 0xd0, 0xd0, 0x5a, 0x4d, 0x80, 0xfb, 0x32,
+    0x13, 0x75, 0x7d, 0xae, 0x08, 0x6f, 0x22, 0x15, 0x5d, 0x4d, 0xc3, 0xb3, 0x12, 0x58, 0x4e, 0xd6,
+];
+const SECP256K1_SIGNATURE: [u8; 64] = [
+    0xa4, 0xf0, 0x6f, 0x65, 0x59, 0x4e, 0xee, 0x70, 0x78, 0x95, 0xcd, 0xcd, 0x4e, 0x5b, 0x96, 0x73,
+    0x22, 0x4d, 0xfa, 0x94, 0x71, 0x8e, 0xfc, 0x9a, 0xc1, 0x3e, 0x4b, 0xa0, 0x1f, 0x16, 0x74, 0x41,
+    0x00, 0xb3, 0xb3, 0xd8, 0x32, 0x73, 0x24, 0x54, 0xc6, 0xd2, 0x59, 0x0f, 0x0f, 0x48, 0x8e, 0x92,
+    0x84, 0x4a, 0x23, 0xa1, 0xec, 0xa7, 0xb5, 0x54, 0xef, 0x02, 0xe1, 0xa2, 0x8b, 0x90, 0xcb, 0x2a,
+];
+const SECP256K1_RECOVERY_ID: u32 = 0;
+
+/// The keys of the maps the map seeds operate on, and the elements of the
+/// first vector of the vector seeds.
+const MAP_KEYS: [u32; 4] = [1, 2, 3, 5];
+
+/// A deterministic xorshift generator, so that every run writes the same
+/// seeds.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn bytes(&mut self, len: usize) -> std::vec::Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+}
+
+/// Builds input bytes the way `arbitrary` reads them: integers, and the
+/// `u32`s that pick enum variants, little-endian from the front, and each
+/// element of a collection behind a byte whose low bit says that it follows.
+#[derive(Clone, Default)]
+struct Encoder(std::vec::Vec<u8>);
+
+impl Encoder {
+    fn raw(mut self, bytes: &[u8]) -> Self {
+        self.0.extend_from_slice(bytes);
+        self
+    }
+
+    fn u32(self, v: u32) -> Self {
+        self.raw(&v.to_le_bytes())
+    }
+
+    fn u64(self, v: u64) -> Self {
+        self.raw(&v.to_le_bytes())
+    }
+
+    fn bytes(mut self, bytes: &[u8]) -> Self {
+        for b in bytes {
+            self.0.extend_from_slice(&[1, *b]);
+        }
+        self.0.push(0);
+        self
+    }
+}
+
+/// The `u32` that picks variant `i` of an enum with `n` variants, which
+/// derived `Arbitrary` impls pick as `(u32 * n) >> 32`.
+fn choice(i: usize, n: usize) -> u32 {
+    let (i, n) = (i as u64, n as u64);
+    (i << 32).div_ceil(n) as u32
+}
+
+/// The variants of an enum, in order, each with the `u32` that picks it,
+/// given the name of the variant each `u32` picks. Each variant is picked by
+/// one range of `u32`s, so the end of each range is found by binary search.
+fn variants(name_at: impl Fn(u32) -> String) -> std::vec::Vec<(String, u32)> {
+    let mut variants = std::vec::Vec::new();
+    let mut start = 0u32;
+    loop {
+        let name = name_at(start);
+        let (mut lo, mut hi) = (start, u32::MAX);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2 + 1;
+            if name_at(mid) == name {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        variants.push((name, start));
+        if lo == u32::MAX {
+            return variants;
+        }
+        start = lo + 1;
+    }
+}
+
+/// The name of the variant of `v`, from its `Debug`.
+fn variant_name<T: Debug>(v: &T) -> String {
+    let debug = format!("{v:?}");
+    debug.split(['(', ' ', '{']).next().unwrap().to_string()
+}
+
+/// Encodes a `T` as a variant selector followed by `operands`, returning
+/// the first encoding that decodes, to the last byte, to a `T` that
+/// `is_expected` accepts. Derived enums pick their variant with a `u32`, but
+/// the SDK picks some with narrower integers, so selectors of each width are
+/// tried.
+fn encode<T: for<'a> Arbitrary<'a> + Debug>(
+    operands: &Encoder,
+    is_expected: impl Fn(&T) -> bool,
+) -> Option<Encoder> {
+    let starts = variants(|d| {
+        variant_name(&T::arbitrary(&mut Unstructured::new(&d.to_le_bytes())).unwrap())
+    });
+    let selectors = starts.iter().map(|(_, d)| *d).chain([u32::MAX]);
+
+    selectors
+        .flat_map(|d| [4, 2, 1].map(|width| d.to_le_bytes()[..width].to_vec()))
+        .map(|selector| Encoder(selector).raw(&operands.0))
+        .find(|encoded| {
+            let mut u = Unstructured::new(&encoded.0);
+            T::arbitrary(&mut u).is_ok_and(|v| u.is_empty() && is_expected(&v))
+        })
+}
+
+/// The bytes of a `FuzzInstructionPrototype` that pick each host function,
+/// keyed by its name as `FuzzInstructionPrototype::name` gives it.
+fn host_functions() -> BTreeMap<String, std::vec::Vec<u8>> {
+    let name_of = |bytes: &[u8], part: usize| {
+        let instr = FuzzInstructionPrototype::arbitrary(&mut Unstructured::new(bytes)).unwrap();
+        instr.name().split("::").nth(part).unwrap().to_string()
+    };
+
+    let mut fns = BTreeMap::new();
+    for (_, kind) in variants(|d| name_of(&d.to_le_bytes(), 0)) {
+        let kind = Encoder::default().u32(kind);
+        for (_, module) in variants(|d| name_of(&kind.clone().u32(d).0, 1)) {
+            let module = kind.clone().u32(module);
+            for (_, func) in variants(|d| name_of(&module.clone().u32(d).0, 2)) {
+                let bytes = module.clone().u32(func).0;
+                let instr =
+                    FuzzInstructionPrototype::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+                fns.insert(instr.name(), bytes);
+            }
+        }
+    }
+    fns
+}
+
+/// The smallest valid contract wasm: a module holding only the interface
+/// version the host requires.
+fn minimal_contract_wasm() -> std::vec::Vec<u8> {
+    // An `ScEnvMetaEntry::ScEnvMetaKindInterfaceVersion`, as XDR.
+    let mut meta = 0u32.to_be_bytes().to_vec();
+    meta.extend_from_slice(&INTERFACE_VERSION.to_be_bytes());
+
+    let mut section = vec![ENV_META_V0_SECTION_NAME.len() as u8];
+    section.extend_from_slice(ENV_META_V0_SECTION_NAME.as_bytes());
+    section.extend_from_slice(&meta);
+
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+    wasm.push(0); // A custom section.
+    wasm.push(section.len() as u8);
+    wasm.extend_from_slice(&section);
+    wasm
+}
+
+/// Encodes `val` as its prototype, the variant selector followed by
+/// `operands`.
+fn encode_val(env: &Env, val: Val, operands: &Encoder) -> Encoder {
+    encode::<<Val as SorobanArbitrary>::Prototype>(operands, |v| {
+        Val::from_val(env, v).get_payload() == val.get_payload()
+    })
+    .expect("an encoding of the Val")
+}
+
+/// Encodes the `Val` variant of an operand of a typed instruction, which
+/// may otherwise name a register, holding the already encoded `value`.
+fn val_operand<T: for<'a> Arbitrary<'a> + Debug>(value: &Encoder) -> Encoder {
+    encode::<T>(value, |v| format!("{v:?}").starts_with("Val("))
+        .expect("an encoding of the operand")
+}
+
+/// Operands of typed host functions that succeed: the name of the host
+/// function and its operands, encoded.
+fn crafted_operands(env: &Env) -> std::vec::Vec<(&'static str, Encoder)> {
+    let u32_val = |v: u32| encode_val(env, v.into_val(env), &Encoder::default().u32(v));
+    let mut expected = Map::<Val, Val>::new(env);
+    let mut entries = Encoder::default();
+    for k in MAP_KEYS {
+        expected.set(k.into_val(env), (k * 10).into_val(env));
+        entries = entries.raw(&[1]).raw(&u32_val(k).0).raw(&u32_val(k * 10).0);
+    }
+    let map = encode::<<Map<Val, Val> as SorobanArbitrary>::Prototype>(&entries.raw(&[0]), |m| {
+        Map::<Val, Val>::from_val(env, m) == expected
+    })
+    .expect("an encoding of the map");
+
+    let map = val_operand::<MapOperandPrototype>(&map);
+    let bytes = |b: &[u8]| val_operand::<BytesOperandPrototype>(&Encoder::default().bytes(b)).0;
+    let key = |k: u32| val_operand::<ValOperandPrototype>(&u32_val(k)).0;
+
+    let mut operands = vec![
+        (
+            "Typed::Crypto::VerifySigEd25519",
+            Encoder::default()
+                .raw(&bytes(&ED25519_PUBLIC_KEY))
+                .raw(&bytes(&ED25519_MESSAGE))
+                .raw(&bytes(&ED25519_SIGNATURE)),
+        ),
+        (
+            "Typed::Crypto::RecoverKeyEcdsaSecp256k1",
+            Encoder::default()
+                .raw(&bytes(&SECP256K1_DIGEST))
+                .raw(&bytes(&SECP256K1_SIGNATURE))
+                .u32(SECP256K1_RECOVERY_ID),
+        ),
+        (
+            "Typed::Ledger::UploadWasm",
+            Encoder::default().raw(&bytes(&minimal_contract_wasm())),
+        ),
+    ];
+    for k in MAP_KEYS {
+        operands.extend([
+            ("Typed::Map::MapGet", map.clone().raw(&key(k))),
+            ("Typed::Map::MapHas", map.clone().raw(&key(k))),
+            ("Typed::Map::MapDel", map.clone().raw(&key(k))),
+            ("Typed::Map::MapPut", map.clone().raw(&key(k)).raw(&key(k))),
+        ]);
+    }
+    operands
+}
+
+/// Frames a program of instructions, each encoded as a
+/// `FuzzInstructionPrototype`, as an input of `target`, or returns `None` if
+/// `target` cannot run it.
+fn frame(target: &str, rng: &mut Rng, program: &[std::vec::Vec<u8>]) -> Option<std::vec::Vec<u8>> {
+    let typed = choice(1, 2).to_le_bytes();
+    let is_typed = |instr: &std::vec::Vec<u8>| instr[..4] == typed;
+    let mut input = std::vec::Vec::new();
+    match target {
+        "rawval_fuzz" | "typed_fuzz" => {
+            if program
+                .iter()
+                .any(|i| is_typed(i) != (target == "typed_fuzz"))
+            {
+                return None;
+            }
+            for instr in program {
+                input.push(1);
+                input.extend_from_slice(&instr[4..]);
+            }
+        }
+        "diff_fuzz" => {
+            let [instr] = program else { return None };
+            input.extend_from_slice(instr);
+        }
+        "budget_fuzz" => {
+            let [instr] = program else { return None };
+            if !is_typed(instr) {
+                return None;
+            }
+            // The CPU and memory limits.
+            input.extend(rng.bytes(8));
+            input.extend_from_slice(&instr[4..]);
+        }
+        "replay_fuzz" => {
+            // The PRNG seed, ledger advance, timestamp and network ID.
+            input.extend(rng.bytes(32 + 1 + 8 + 32));
+            for instr in program {
+                input.push(1);
+                input.extend_from_slice(instr);
+            }
+        }
+        _ => return None,
+    }
+    Some(input)
+}
+
+/// Decodes an input of `target` that `frame` can write into the program the
+/// target runs for it.
+fn program_of(target: &str, input: &[u8]) -> Option<std::vec::Vec<FuzzInstructionPrototype>> {
+    match target {
+        "rawval_fuzz" | "typed_fuzz" => decode_program(target, input).ok(),
+        "diff_fuzz" => {
+            let instr = FuzzInstructionPrototype::arbitrary_take_rest(Unstructured::new(input));
+            Some(vec![instr.ok()?])
+        }
+        "budget_fuzz" => {
+            let u = Unstructured::new(input.get(8..)?);
+            let instr = TypedFuzzInstructionPrototype::arbitrary_take_rest(u).ok()?;
+            Some(vec![FuzzInstructionPrototype::Typed(instr)])
+        }
+        "replay_fuzz" => {
+            let u = Unstructured::new(input.get(32 + 1 + 8 + 32..)?);
+            std::vec::Vec::<FuzzInstructionPrototype>::arbitrary_take_rest(u).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Whether `input` decodes to exactly the host functions of `names`.
+fn calls(target: &str, input: &[u8], names: &[&str]) -> bool {
+    program_of(target, input).is_some_and(|program| {
+        program
+            .iter()
+            .map(FuzzInstructionPrototype::name)
+            .collect::<std::vec::Vec<_>>()
+            == names
+    })
+}
+
+/// Whether the typed instruction runs without error.
+fn succeeds(instr: &[u8]) -> bool {
+    let Ok(instr) = FuzzInstructionPrototype::arbitrary_take_rest(Unstructured::new(instr)) else {
+        return false;
+    };
+    let env = Env::default();
+    let contract_id = register_fuzz_contract(&env);
+    let client = fuzzcontract::Client::new(&env, &contract_id);
+    let mut instructions = Vec::new(&env);
+    instructions.push_back(instr.to_guest(&env));
+    let program = FuzzProgram { instructions };
+
+    matches!(
+        fuzz_catch_panic(|| client.try_run_program(&program)),
+        Ok(Ok(Ok(_)))
+    )
+}
+
+/// Seeds for the targets that run instructions, keyed by file name.
+fn instruction_seeds(
+    env: &Env,
+    target: &str,
+    rng: &mut Rng,
+) -> BTreeMap<String, std::vec::Vec<u8>> {
+    let mut seeds = BTreeMap::new();
+    let file_name = |name: &str, i: usize| format!("seed-{}-{i}", name.replace("::", "-"));
+    let fns = host_functions();
+
+    for (name, selector) in &fns {
+        let mut written = 0;
+        for attempt in 0..=RANDOM_ATTEMPTS {
+            if written > RANDOM_SEEDS {
+                break;
+            }
+            let mut instr = selector.clone();
+            if attempt > 0 {
+                let len = rng.below(48);
+                instr.extend(rng.bytes(len));
+            }
+            let Some(input) = frame(target, rng, &[instr]) else {
+                break;
+            };
+            if calls(target, &input, &[name]) {
+                seeds.insert(file_name(name, written), input);
+                written += 1;
+            }
+        }
+    }
+
+    let mut crafted: BTreeMap<&str, usize> = BTreeMap::new();
+    for (name, operands) in crafted_operands(env) {
+        let Some(selector) = fns.get(name) else {
+            eprintln!("warning: no host function {name}");
+            continue;
+        };
+        let instr = [selector.as_slice(), &operands.0].concat();
+        if !succeeds(&instr) {
+            eprintln!("warning: the crafted {name} seed fails");
+            continue;
+        }
+        let Some(input) = frame(target, rng, &[instr]) else {
+            continue;
+        };
+        if !calls(target, &input, &[name]) {
+            eprintln!("warning: the crafted {name} seed for {target} does not decode");
+            continue;
+        }
+        let i = crafted.entry(name).or_default();
+        seeds.insert(file_name(&format!("{name}::Crafted"), *i), input);
+        *i += 1;
+    }
+    seeds
+}
+
+/// Finds the operands of the variant of `T` that `selector` picks, as the
+/// fewest of `pieces` that it reads: each piece is an encoding of a value
+/// and the value as `Debug` shows it. The variant must read the pieces to
+/// their last byte, without reading past them, and show each of their
+/// values.
+fn operand_layout<T: for<'a> Arbitrary<'a> + Debug>(
+    selector: &Encoder,
+    pieces: &[(Encoder, String)],
+) -> Option<std::vec::Vec<usize>> {
+    const PADDING: [u8; 16] = [0xff; 16];
+    const MAX_OPERANDS: usize = 3;
+
+    let mut layouts: std::vec::Vec<std::vec::Vec<usize>> = vec![vec![]];
+    for _ in 0..=MAX_OPERANDS {
+        for layout in &layouts {
+            let mut encoded = selector.clone();
+            for &i in layout {
+                encoded = encoded.raw(&pieces[i].0 .0);
+            }
+            let padded = encoded.raw(&PADDING).0;
+            let mut u = Unstructured::new(&padded);
+            let Ok(v) = T::arbitrary(&mut u) else {
+                continue;
+            };
+            let debug = format!("{v:?}");
+            if u.len() == PADDING.len() && layout.iter().all(|&i| debug.contains(&pieces[i].1)) {
+                return Some(layout.clone());
+            }
+        }
+        layouts = layouts
+            .iter()
+            .flat_map(|l| (0..pieces.len()).map(move |i| [l.as_slice(), &[i]].concat()))
+            .collect();
+    }
+    None
+}
+
+/// Seeds for `vec_fuzz` or `map_fuzz`, given the encoded collections their
+/// `TestCases` start with: for each variant of `Op` and each `Mode`, ten of
+/// the operation on `elements`, and on indexes into them. The operands of
+/// each operation are found with `operand_layout`. `decode` decodes an input
+/// into the `Debug` of its operations and the name of its mode, or returns
+/// `None` if it does not decode to the collections; an input that does not
+/// decode to what it was built from is reported and left out.
+fn operation_seeds<Op: for<'a> Arbitrary<'a> + Debug>(
+    target: &str,
+    collections: &Encoder,
+    elements: &[u64],
+    decode: impl Fn(&[u8]) -> Option<(std::vec::Vec<String>, String)>,
+) -> BTreeMap<String, std::vec::Vec<u8>> {
+    // A `FuzzPointerPrototype::Scratch` with no bytes.
+    let pointer = |offset: u32| {
+        encode::<FuzzPointerPrototype>(
+            &Encoder::default().u32(offset).raw(&[0]),
+            |p| matches!(p, FuzzPointerPrototype::Scratch(o, _) if *o == offset),
+        )
+        .expect("an encoding of the pointer")
+    };
+    // Each kind of operand, with a value that stands out in `Debug` to find
+    // it by, and the operand the `j`th operation gets.
+    type Operand<'a> = (Encoder, String, Box<dyn Fn(usize) -> Encoder + 'a>);
+    let (index, element, offset) = (0x5eed_0001u32, 0x5eed_0000_0000_0002u64, 0x5eed_0003u32);
+    let operands: [Operand; 3] = [
+        (
+            Encoder::default().u32(index),
+            index.to_string(),
+            Box::new(|j| Encoder::default().u32((j % elements.len()) as u32)),
+        ),
+        (
+            Encoder::default().u64(element),
+            element.to_string(),
+            Box::new(|j| Encoder::default().u64(elements[j % elements.len()])),
+        ),
+        (
+            pointer(offset),
+            offset.to_string(),
+            Box::new(|j| pointer(j as u32 * 8)),
+        ),
+    ];
+    let pieces: std::vec::Vec<(Encoder, String)> = operands
+        .iter()
+        .map(|(encoded, debug, _)| (encoded.clone(), debug.clone()))
+        .collect();
+
+    let op_of =
+        |d: u32| variant_name(&Op::arbitrary(&mut Unstructured::new(&d.to_le_bytes())).unwrap());
+    let mode_of =
+        |d: u32| variant_name(&Mode::arbitrary(&mut Unstructured::new(&d.to_le_bytes())).unwrap());
+    let mut seeds = BTreeMap::new();
+    for (name, selector) in variants(op_of) {
+        let selector = Encoder::default().u32(selector);
+        let Some(layout) = operand_layout::<Op>(&selector, &pieces) else {
+            eprintln!("warning: no operands of {name} for {target}");
+            continue;
+        };
+        let ops: std::vec::Vec<std::vec::Vec<u8>> = (0..10)
+            .map(|j| {
+                let op = layout
+                    .iter()
+                    .fold(selector.clone(), |op, &i| op.raw(&(operands[i].2)(j).0));
+                op.0
+            })
+            .collect();
+        let expected: std::vec::Vec<String> = ops
+            .iter()
+            .map(|op| {
+                let op = Op::arbitrary(&mut Unstructured::new(op)).expect("an operation");
+                format!("{op:?}")
+            })
+            .collect();
+
+        for (mode, selector) in variants(mode_of) {
+            let input = collections.clone().raw(&ops.concat()).u32(selector).0;
+            if decode(&input) != Some((expected.clone(), mode.clone())) {
+                eprintln!("warning: the {name} {mode} seed for {target} does not decode");
+                continue;
+            }
+            seeds.insert(format!("seed-{name}-{mode}"), input);
+        }
+    }
+    seeds
+}
+
+/// Seeds for `vec_fuzz` or `map_fuzz`: see `operation_seeds`. The vectors
+/// are `MAP_KEYS` and another, and the map maps each of `MAP_KEYS` to ten
+/// times itself.
+fn collection_seeds(env: &Env, target: &str) -> BTreeMap<String, std::vec::Vec<u8>> {
+    let encode_vec = |elements: &[u64]| {
+        let mut encoded = Encoder::default();
+        for e in elements {
+            encoded = encoded.raw(&[1]).u64(*e);
+        }
+        encode::<<Vec<u64> as SorobanArbitrary>::Prototype>(&encoded.raw(&[0]), |v| {
+            Vec::<u64>::from_val(env, v) == Vec::from_slice(env, elements)
+        })
+        .expect("an encoding of the vector")
+    };
+    let elements: std::vec::Vec<u64> = MAP_KEYS.map(u64::from).to_vec();
+    if target == "vec_fuzz" {
+        let second = [8, 13];
+        let collections = encode_vec(&elements).raw(&encode_vec(&second).0);
+        return operation_seeds::<vec::TypedModVecPrototype>(
+            target,
+            &collections,
+            &elements,
+            |input| {
+                let t = vec::TestCases::arbitrary_take_rest(Unstructured::new(input)).ok()?;
+                let holds = |v, elements: &[u64]| {
+                    Vec::<u64>::from_val(env, v) == Vec::from_slice(env, elements)
+                };
+                (holds(&t.vec_0, &elements) && holds(&t.vec_1, &second)).then(|| {
+                    let tests = t.tests.iter().map(|t| format!("{t:?}")).collect();
+                    (tests, variant_name(&t.mode))
+                })
+            },
+        );
+    }
+
+    let mut map = Map::new(env);
+    let mut entries = Encoder::default();
+    for k in &elements {
+        map.set(*k, k * 10);
+        entries = entries.raw(&[1]).u64(*k).u64(k * 10);
+    }
+    let collections =
+        encode::<<Map<u64, u64> as SorobanArbitrary>::Prototype>(&entries.raw(&[0]), |m| {
+            Map::<u64, u64>::from_val(env, m) == map
+        })
+        .expect("an encoding of the map");
+    operation_seeds::<map::TypedModMapPrototype>(target, &collections, &elements, |input| {
+        let t = map::TestCases::arbitrary_take_rest(Unstructured::new(input)).ok()?;
+        (Map::<u64, u64>::from_val(env, &t.map) == map).then(|| {
+            let tests = t.tests.iter().map(|t| format!("{t:?}")).collect();
+            (tests, variant_name(&t.mode))
+        })
+    })
+}
+
+/// Seeds for `roundtrip_fuzz`, keyed by file name: each kind of round trip
+/// of a few values, a scalar, a vec and a map. Each seed is decoded again,
+/// and one that does not decode to its round trip is reported and left out.
+fn roundtrip_seeds(env: &Env) -> BTreeMap<String, std::vec::Vec<u8>> {
+    let node = |n: ScValNode, operands: Encoder| {
+        let encoded = encode::<ScValNode>(&operands, |m| format!("{m:?}") == format!("{n:?}"))
+            .expect("an encoding of the node");
+        (n, encoded)
+    };
+    let values = [
+        (
+            "U32",
+            vec![node(ScValNode::U32(7), Encoder::default().u32(7))],
+        ),
+        (
+            "Vec",
+            vec![
+                node(ScValNode::Vec(Some(2)), Encoder::default().raw(&[1, 2])),
+                node(ScValNode::U32(1), Encoder::default().u32(1)),
+                node(
+                    ScValNode::Symbol(b"abc".to_vec()),
+                    Encoder::default().bytes(b"abc"),
+                ),
+            ],
+        ),
+        (
+            "Map",
+            vec![
+                node(ScValNode::Map(Some(1)), Encoder::default().raw(&[1, 1])),
+                node(ScValNode::U32(1), Encoder::default().u32(1)),
+                node(ScValNode::I64(-2), Encoder::default().u64(-2i64 as u64)),
+            ],
+        ),
+    ];
+    let no_corruption =
+        encode::<ScValCorruption>(&Encoder::default(), |c| matches!(c, ScValCorruption::None))
+            .expect("an encoding of no corruption");
+
+    let mut round_trips = vec![(
+        "Serialize-U32".to_string(),
+        encode::<FuzzRoundTripPrototype>(
+            &encode_val(env, 7u32.into_val(env), &Encoder::default().u32(7)),
+            |r| matches!(r, FuzzRoundTripPrototype::Serialize(_)),
+        ),
+    )];
+    for (name, nodes) in values {
+        let mut encoded = Encoder::default();
+        for (_, n) in &nodes {
+            encoded = encoded.raw(&[1]).raw(&n.0);
+        }
+        let encoded = encoded.raw(&[0]).raw(&no_corruption.0);
+        let value = ScValBytesPrototype {
+            nodes: nodes.into_iter().map(|(n, _)| n).collect(),
+            corruption: ScValCorruption::None,
+        };
+        let xdr = value.to_xdr();
+
+        round_trips.push((
+            format!("Deserialize-{name}"),
+            encode::<FuzzRoundTripPrototype>(
+                &encoded,
+                |r| matches!(r, FuzzRoundTripPrototype::Deserialize(v) if v.to_xdr() == xdr),
+            ),
+        ));
+        round_trips.push((
+            format!("DeserializeRaw-{name}"),
+            encode::<FuzzRoundTripPrototype>(&Encoder::default().bytes(&xdr), |r| {
+                matches!(r, FuzzRoundTripPrototype::DeserializeRaw(b)
+                    if Bytes::from_val(env, b) == Bytes::from_slice(env, &xdr))
+            }),
+        ));
+    }
+
+    let mut seeds = BTreeMap::new();
+    for (name, round_trip) in round_trips {
+        let Some(round_trip) = round_trip else {
+            eprintln!("warning: no encoding of the {name} round trip");
+            continue;
+        };
+        let input = Encoder::default().raw(&[1]).raw(&round_trip.0).0;
+        let expected = FuzzRoundTripPrototype::arbitrary(&mut Unstructured::new(&round_trip.0));
+        let decoded =
+            std::vec::Vec::<FuzzRoundTripPrototype>::arbitrary_take_rest(Unstructured::new(&input));
+        if format!("{decoded:?}") != format!("{:?}", expected.map(|r| vec![r])) {
+            eprintln!("warning: the {name} seed for roundtrip_fuzz does not decode");
+            continue;
+        }
+        seeds.insert(format!("seed-{name}"), input);
+    }
+    seeds
+}
+
+fn main() -> ExitCode {
+    let args: std::vec::Vec<String> = std::env::args().skip(1).collect();
+    let targets: std::vec::Vec<&str> = if args.is_empty() {
+        TARGETS.to_vec()
+    } else {
+        args.iter().map(String::as_str).collect()
+    };
+    if let Some(target) = targets.iter().find(|t| !TARGETS.contains(t)) {
+        eprintln!("unknown target {target}");
+        eprintln!("usage: seed_corpus [{}]...", TARGETS.join("|"));
+        return ExitCode::FAILURE;
+    }
+
+    let env = Env::default();
+    for target in targets {
+        let mut rng = Rng(0x5eed_c0de_5eed_c0de);
+        let seeds = match target {
+            "vec_fuzz" | "map_fuzz" => collection_seeds(&env, target),
+            "roundtrip_fuzz" => roundtrip_seeds(&env),
+            _ => instruction_seeds(&env, target, &mut rng),
+        };
+
+        let dir = Path::new(CORPUS_DIR).join(target);
+        let written = std::fs::create_dir_all(&dir).and_then(|()| {
+            for (name, input) in &seeds {
+                std::fs::write(dir.join(name), input)?;
+            }
+            Ok(())
+        });
+        if let Err(e) = written {
+            eprintln!("{}: {e}", dir.display());
+            return ExitCode::FAILURE;
+        }
+        println!("{target}: {} seeds in {}", seeds.len(), dir.display());
+    }
+
+    ExitCode::SUCCESS
+}
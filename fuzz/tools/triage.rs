@@ -0,0 +1,176 @@
+//! Sorts crash artifacts from `rawval_fuzz` or `typed_fuzz` into buckets
+//! that are likely the same bug.
+//!
+//! Each artifact is run again the way the target runs it, with every check
+//! the target makes. The crash is put down to the first instruction at which
+//! a prefix of the program crashes, and artifacts land in the same bucket if
+//! that instruction calls the same host function, named by its module and
+//! variant, and the crash is the same: a host panic carrying the same host
+//! error, or the same failed check of the budget or of guest memory. An
+//! instruction that calls into another copy of the contract is bucketed by
+//! the instruction it runs there, followed down any further calls. Each
+//! bucket is listed with its smallest artifact as its representative.
+//!
+//! ```text
+//! cd fuzz && cargo run --bin triage -- typed_fuzz artifacts/typed_fuzz
+//! ```
+
+use contract_for_fuzz_fuzz::{decode_program, panic_message, program, FuzzInstructionPrototype};
+use soroban_sdk::arbitrary::fuzz_catch_panic;
+use soroban_sdk::Env;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Bucket keys for artifacts that did not crash as expected.
+const NOT_AN_INPUT: &str = "(not an input of the target)";
+const NO_CRASH: &str = "(does not reproduce)";
+
+struct Artifact {
+    path: PathBuf,
+    len: usize,
+    /// The first line of the panic message, if there was one.
+    panic: Option<String>,
+}
+
+/// Runs a program the way the target runs an input of `input_len` bytes,
+/// returning the panic message if it crashed.
+fn run(input: &[FuzzInstructionPrototype], input_len: usize) -> Option<String> {
+    let panic_r = fuzz_catch_panic(|| {
+        program::check(Env::default, input, input_len);
+    });
+
+    panic_r.err().map(|e| panic_message(&*e).to_string())
+}
+
+/// What a crash was: the host error a host panic carries, such as
+/// `Error(WasmVm, InternalError)`, or for a failed check the first line of
+/// its message, up to any value it prints and with numbers elided.
+fn failure(msg: &str) -> String {
+    if msg.starts_with("host panicked") {
+        return msg
+            .find("Error(")
+            .and_then(|start| {
+                let end = msg[start..].find(')')?;
+                Some(msg[start..=start + end].to_string())
+            })
+            .unwrap_or_else(|| "(no host error)".to_string());
+    }
+
+    let line = msg.lines().next().unwrap_or_default();
+    let line = line.split('{').next().unwrap_or_default().trim_end();
+    let mut failure = String::new();
+    let mut in_number = false;
+    for c in line.chars() {
+        if !c.is_ascii_digit() {
+            failure.push(c);
+        } else if !in_number {
+            failure.push('N');
+        }
+        in_number = c.is_ascii_digit();
+    }
+    failure
+}
+
+/// Replays an artifact and returns its bucket key and the first line of its
+/// panic message.
+fn triage(target: &str, data: &[u8]) -> (String, Option<String>) {
+    let Ok(input) = decode_program(target, data) else {
+        return (NOT_AN_INPUT.to_string(), None);
+    };
+    if run(&input, data.len()).is_none() {
+        return (NO_CRASH.to_string(), None);
+    }
+
+    // The full program crashes, so some prefix of it is the first to. Each is
+    // checked against the budget for the whole input, as the target would.
+    let (len, msg) = (0..=input.len())
+        .find_map(|len| Some((len, run(&input[..len], data.len())?)))
+        .unwrap();
+    let host_fn = match len {
+        0 => "(empty program)".to_string(),
+        _ => input[len - 1].innermost().name(),
+    };
+    let key = format!("{host_fn}, {}", failure(&msg));
+
+    (key, msg.lines().next().map(str::to_string))
+}
+
+/// Lists the artifacts named on the command line, reading directories one
+/// level deep.
+fn artifact_paths(args: &[String]) -> std::io::Result<std::vec::Vec<PathBuf>> {
+    let mut paths = std::vec::Vec::new();
+    for arg in args {
+        let path = PathBuf::from(arg);
+        if path.is_dir() {
+            let mut entries = std::fs::read_dir(&path)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<std::io::Result<std::vec::Vec<_>>>()?;
+            entries.retain(|p| p.is_file());
+            entries.sort();
+            paths.extend(entries);
+        } else {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+fn main() -> ExitCode {
+    let args: std::vec::Vec<String> = std::env::args().collect();
+    let (target, artifacts) = match args.as_slice() {
+        [_, target, artifacts @ ..] if !artifacts.is_empty() => (target, artifacts),
+        _ => {
+            eprintln!("usage: triage <rawval_fuzz|typed_fuzz> <artifact or directory>...");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Err(e) = decode_program(target, &[]) {
+        eprintln!("{e}");
+        return ExitCode::FAILURE;
+    }
+
+    let paths = match artifact_paths(artifacts) {
+        Ok(paths) => paths,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut buckets: BTreeMap<String, std::vec::Vec<Artifact>> = BTreeMap::new();
+    for path in paths {
+        let data = match std::fs::read(&path) {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("{}: {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        };
+        let (key, panic) = triage(target, &data);
+        buckets.entry(key).or_default().push(Artifact {
+            path,
+            len: data.len(),
+            panic,
+        });
+    }
+
+    let total: usize = buckets.values().map(|b| b.len()).sum();
+    println!("{total} artifacts, {} buckets", buckets.len());
+    for (key, bucket) in &buckets {
+        let representative = bucket.iter().min_by_key(|a| a.len).unwrap();
+        println!();
+        println!("{key}: {} artifacts", bucket.len());
+        println!(
+            "  representative: {} ({} bytes)",
+            representative.path.display(),
+            representative.len
+        );
+        if let Some(panic) = &representative.panic {
+            println!("  panic: {panic}");
+        }
+    }
+
+    ExitCode::SUCCESS
+}
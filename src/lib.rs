@@ -1,11 +1,22 @@
 #![no_std]
+// The raw and typed instruction generators munch every host function one
+// argument at a time, which nests deeper than the default limit.
+#![recursion_limit = "1024"]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Env, Val};
+use soroban_env_common::{
+    AddressObject, Bool, BytesObject, DurationObject, I128Object, I256Object, I256Val, I64Object,
+    I64Val, MapObject, StorageType, StringObject, SymbolObject, TimepointObject, U128Object,
+    U256Object, U256Val, U32Val, U64Object, U64Val, VecObject, Void,
+};
+use soroban_sdk::{contract, contractimpl, contracttype, Bytes, Env, IntoVal, Val, Vec};
 
 pub mod raw;
+pub mod roundtrip;
+pub mod scratch;
 pub mod typed;
 
 use raw::RawFuzzInstruction;
+use roundtrip::FuzzRoundTrip;
 use typed::TypedFuzzInstruction;
 
 #[contracttype]
@@ -23,11 +34,176 @@ impl FakeVal {
     }
 }
 
+/// An instruction argument: either a literal value, or the result of an
+/// earlier instruction in the same `FuzzProgram`.
+#[contracttype]
+#[derive(Copy, Clone, Debug)]
+pub enum FuzzOperand {
+    Val(FakeVal),
+    Reg(u32),
+}
+
+/// The storage durability a ledger instruction reads or writes.
+#[contracttype]
+#[derive(Copy, Clone, Debug)]
+pub enum FuzzStorageType {
+    Temporary,
+    Persistent,
+    Instance,
+}
+
+impl From<FuzzStorageType> for StorageType {
+    fn from(t: FuzzStorageType) -> StorageType {
+        match t {
+            FuzzStorageType::Temporary => StorageType::Temporary,
+            FuzzStorageType::Persistent => StorageType::Persistent,
+            FuzzStorageType::Instance => StorageType::Instance,
+        }
+    }
+}
+
+/// A pointer argument of a linear-memory host function.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum FuzzPointer {
+    /// An offset into the scratch region, wrapped to its length, with bytes
+    /// to write there first, as many as fit (see `scratch::seed`). The
+    /// length passed with it is cut down to fit as well (see `scratch::fit`).
+    Scratch(u32, Bytes),
+    /// A pointer passed as is, which is likely out of bounds, with its
+    /// length passed as is.
+    Wild(u32),
+}
+
+impl FuzzPointer {
+    pub fn to_pos(&self) -> U32Val {
+        match self {
+            FuzzPointer::Scratch(offset, bytes) => U32Val::from(scratch::seed(*offset, bytes)),
+            FuzzPointer::Wild(pos) => U32Val::from(*pos),
+        }
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub enum FuzzInstruction {
     Raw(RawFuzzInstruction),
     Typed(TypedFuzzInstruction),
+    RoundTrip(FuzzRoundTrip),
+}
+
+impl FuzzInstruction {
+    pub fn run(self, env: &Env, regs: &Registers) -> Val {
+        match self {
+            FuzzInstruction::Raw(instr) => instr.run(env, regs),
+            FuzzInstruction::Typed(instr) => instr.run(env, regs),
+            FuzzInstruction::RoundTrip(instr) => instr.run(env),
+        }
+    }
+}
+
+/// A sequence of instructions run inside a single contract invocation,
+/// so host objects created by one instruction are still live for the next.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FuzzProgram {
+    pub instructions: Vec<FuzzInstruction>,
+}
+
+pub const REGISTER_COUNT: usize = 32;
+
+/// The guest-side register file of a running `FuzzProgram`.
+///
+/// Register `n` holds the result of the program's `n`th instruction.
+/// Results past `REGISTER_COUNT` are not recorded.
+pub struct Registers {
+    vals: [Val; REGISTER_COUNT],
+    len: usize,
+}
+
+impl Default for Registers {
+    fn default() -> Registers {
+        Registers {
+            vals: [Val::VOID.to_val(); REGISTER_COUNT],
+            len: 0,
+        }
+    }
+}
+
+impl Registers {
+    /// Resolves an operand to a value.
+    ///
+    /// Register indexes wrap around the registers written so far, so any
+    /// index names some earlier result; with no results yet it reads `Void`.
+    pub fn get(&self, operand: FuzzOperand) -> FakeVal {
+        match operand {
+            FuzzOperand::Val(v) => v,
+            FuzzOperand::Reg(_) if self.len == 0 => FakeVal::from_val(Val::VOID.to_val()),
+            FuzzOperand::Reg(i) => FakeVal::from_val(self.vals[i as usize % self.len]),
+        }
+    }
+
+    pub fn push(&mut self, v: Val) {
+        if self.len < REGISTER_COUNT {
+            self.vals[self.len] = v;
+            self.len += 1;
+        }
+    }
+}
+
+/// Converts a host function's return value to the `Val` reported back to
+/// the harness.
+pub trait SyscallResult {
+    fn into_result(self, env: &Env) -> Val;
+}
+
+macro_rules! impl_syscall_result_for_val_types {
+    ($($t:ty),*) => {
+        $(
+            impl SyscallResult for $t {
+                fn into_result(self, _env: &Env) -> Val {
+                    self.into()
+                }
+            }
+        )*
+    };
+}
+
+impl_syscall_result_for_val_types!(
+    Val,
+    Void,
+    Bool,
+    U32Val,
+    U64Val,
+    I64Val,
+    U256Val,
+    I256Val,
+    AddressObject,
+    BytesObject,
+    DurationObject,
+    I64Object,
+    I128Object,
+    I256Object,
+    MapObject,
+    StringObject,
+    SymbolObject,
+    TimepointObject,
+    U64Object,
+    U128Object,
+    U256Object,
+    VecObject
+);
+
+impl SyscallResult for u64 {
+    fn into_result(self, env: &Env) -> Val {
+        self.into_val(env)
+    }
+}
+
+impl SyscallResult for i64 {
+    fn into_result(self, env: &Env) -> Val {
+        self.into_val(env)
+    }
 }
 
 #[contract]
@@ -35,18 +211,40 @@ pub struct FuzzContract;
 
 #[contractimpl]
 impl FuzzContract {
-    pub fn run(env: Env, fuzz_instruction: FuzzInstruction) {
-        match fuzz_instruction {
-            FuzzInstruction::Raw(instr) => {
-                instr.run();
-            }
-            FuzzInstruction::Typed(instr) => {
-                instr.run(&env);
-            }
+    /// Runs one instruction and returns the host function's result.
+    pub fn run(env: Env, fuzz_instruction: FuzzInstruction) -> Val {
+        scratch::reset();
+        fuzz_instruction.run(&env, &Registers::default())
+    }
+
+    /// Runs a program and returns the result of every instruction, in order.
+    pub fn run_program(env: Env, program: FuzzProgram) -> Vec<Val> {
+        scratch::reset();
+        let mut regs = Registers::default();
+        let mut results = Vec::new(&env);
+        for instr in program.instructions.iter() {
+            let v = instr.run(&env, &regs);
+            regs.push(v);
+            results.push_back(v);
         }
+        results
     }
 }
 
+/// The number of copies of `FuzzContract` the fuzz targets and the
+/// regression tests register, for `call` and `try_call` to call into.
+pub const FUZZ_CONTRACTS: u8 = 3;
+
+/// The address the `i`th copy of `FuzzContract` is registered at.
+#[cfg(not(target_family = "wasm"))]
+pub fn fuzz_contract_address(env: &Env, i: u8) -> soroban_sdk::Address {
+    use soroban_sdk::xdr::{Hash, ScAddress};
+    use soroban_sdk::TryFromVal;
+
+    let id = ScAddress::Contract(Hash([i + 1; 32]));
+    soroban_sdk::Address::try_from_val(env, &id).unwrap()
+}
+
 // duplicated code from guest.rs to generate syscall defs
 mod syscalls {
     #![allow(unused)]
@@ -77,6 +275,32 @@ mod syscalls {
         };
     }
 
+    // This is a helper macro used only by generate_extern_modules below. It
+    // consumes a token-tree of the same form as extern_function_helper and
+    // produces the wrapper the instructions call. The wrapper takes the
+    // contract's `Env` in addition to the host function's arguments: on wasm
+    // it calls straight through to the extern function and ignores the `Env`,
+    // and elsewhere it calls the same host function through the native `Env`,
+    // so the contract can also be registered natively with `register_contract`.
+    macro_rules! syscall_function_helper {
+        {
+            $(#[$attr:meta])* fn $fn_id:ident($($arg:ident:$type:ty),*) -> $ret:ty
+        }
+        =>
+        {
+            #[cfg(target_family = "wasm")]
+            #[inline(always)]
+            pub(crate) unsafe fn $fn_id(_env: &Env, $($arg:$type),*) -> $ret {
+                ext::$fn_id($($arg),*)
+            }
+
+            #[cfg(not(target_family = "wasm"))]
+            pub(crate) unsafe fn $fn_id(env: &Env, $($arg:$type),*) -> $ret {
+                soroban_env_common::Env::$fn_id(env, $($arg),*).unwrap()
+            }
+        };
+    }
+
     // This is a callback macro that pattern-matches the token-tree passed by the
     // x-macro (call_macro_with_all_host_functions) and produces a set of mod
     // items containing extern "C" blocks, each containing extern function
@@ -108,7 +332,7 @@ mod syscalls {
 
         {
             // This macro expands to a set of mod items, each declaring all the extern fns
-            // available for the `impl Env for Guest` methods above to call through to.
+            // and the wrappers the instructions call through to.
             $(
                 // Unlike the other uses of the x-macro that "flatten" the
                 // mod-and-fn structure of the matched token-tree, this callback
@@ -123,18 +347,29 @@ mod syscalls {
                     use soroban_env_common::{I128Object, I256Object, I256Val, I64Object, I64Val, U128Object, U256Object, U256Val, U32Val, U64Object, U64Val, StorageType, TimepointObject, DurationObject};
                     #[allow(unused_imports)]
                     use soroban_env_common::{Void,AddressObject,SymbolObject,StringObject,Bool};
-                    #[link(wasm_import_module = $mod_str)]
-                    extern "C" {
-                        $(
-                            // This invokes the extern_function_helper! macro above
-                            // passing only the relevant parts of the declaration
-                            // matched by the inner pattern above. It is embedded in
-                            // one `$()*` pattern-repetition expander so that it
-                            // repeats only for the part of each mod that the
-                            // corresponding pattern-repetition matcher.
-                            extern_function_helper!{$fn_str, $(#[$fn_attr])* fn $fn_id $args -> $ret}
-                        )*
+                    use soroban_sdk::Env;
+
+                    #[cfg(target_family = "wasm")]
+                    mod ext {
+                        use super::*;
+
+                        #[link(wasm_import_module = $mod_str)]
+                        extern "C" {
+                            $(
+                                // This invokes the extern_function_helper! macro above
+                                // passing only the relevant parts of the declaration
+                                // matched by the inner pattern above. It is embedded in
+                                // one `$()*` pattern-repetition expander so that it
+                                // repeats only for the part of each mod that the
+                                // corresponding pattern-repetition matcher.
+                                extern_function_helper!{$fn_str, $(#[$fn_attr])* fn $fn_id $args -> $ret}
+                            )*
+                        }
                     }
+
+                    $(
+                        syscall_function_helper!{$(#[$fn_attr])* fn $fn_id $args -> $ret}
+                    )*
                 }
             )*
         };
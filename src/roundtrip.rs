@@ -0,0 +1,41 @@
+use crate::{syscalls, FakeVal};
+use soroban_sdk::{contracttype, Bytes, Env, IntoVal, Val};
+
+/// A round trip through the host's XDR serialization, which must come back
+/// to where it started.
+///
+/// If a step fails, so does the round trip. Otherwise it returns the
+/// `obj_cmp` of its two ends, which the harness checks is zero.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum FuzzRoundTrip {
+    /// Serializes a value and deserializes the bytes, comparing the result
+    /// with the value.
+    Serialize(FakeVal),
+    /// Deserializes bytes and serializes the result, comparing the bytes
+    /// with the original ones, which they match only if the original ones
+    /// are the canonical encoding of what they decode to.
+    Deserialize(Bytes),
+    /// As `Deserialize`, for arbitrary bytes rather than the encoding of a
+    /// value, which the deserializer mostly rejects early.
+    DeserializeRaw(Bytes),
+}
+
+impl FuzzRoundTrip {
+    pub fn run(self, env: &Env) -> Val {
+        let cmp = match self {
+            FuzzRoundTrip::Serialize(v) => unsafe {
+                let v = v.to_val();
+                let bytes = syscalls::buf::serialize_to_bytes(env, v);
+                let back = syscalls::buf::deserialize_from_bytes(env, bytes);
+                syscalls::context::obj_cmp(env, v, back)
+            },
+            FuzzRoundTrip::Deserialize(bytes) | FuzzRoundTrip::DeserializeRaw(bytes) => unsafe {
+                let v = syscalls::buf::deserialize_from_bytes(env, bytes.to_object());
+                let back = syscalls::buf::serialize_to_bytes(env, v);
+                syscalls::context::obj_cmp(env, bytes.to_val(), back.to_val())
+            },
+        };
+        cmp.into_val(env)
+    }
+}
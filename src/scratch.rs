@@ -0,0 +1,131 @@
+//! The scratch region: guest memory for the linear-memory host functions to
+//! read and write, so their pointer arguments can point at something.
+//!
+//! It keeps its contents between the instructions of a program, so an
+//! instruction can read what an earlier one wrote. The host functions that
+//! write to guest memory return what they did to it, for the harness to
+//! check. A host function passed a `Scratch` pointer is passed a length
+//! that fits in the region past it, so only `Wild` pointers reach the rest
+//! of guest memory.
+//!
+//! Only the wasm build has guest memory: a natively registered contract runs
+//! in a frame the host refuses every linear-memory call from. A host function
+//! that fails traps the wasm guest, discarding its memory, so only what
+//! successful calls write can be seen at all.
+
+use crate::FuzzPointer;
+use core::ptr::addr_of_mut;
+use soroban_sdk::{contracttype, Bytes, Env, IntoVal, Val};
+
+/// The length of the scratch region.
+pub const SCRATCH_LEN: usize = 1024;
+
+/// The width of a `Val` in guest memory, the unit of the lengths passed
+/// with `vals_pos`.
+pub const VAL_LEN: u32 = 8;
+
+static mut SCRATCH: [u8; SCRATCH_LEN] = [0; SCRATCH_LEN];
+
+/// The host functions that write to guest memory.
+pub const COPY_OUT_FUNCTIONS: &[&str] = &[
+    "bytes_copy_to_linear_memory",
+    "string_copy_to_linear_memory",
+    "symbol_copy_to_linear_memory",
+    "vec_unpack_to_linear_memory",
+    "map_unpack_to_linear_memory",
+];
+
+// Instructions run one at a time, and the host only touches the scratch
+// region during the host function it is passed to, so no two of these
+// borrows overlap.
+fn scratch() -> &'static mut [u8; SCRATCH_LEN] {
+    unsafe { &mut *addr_of_mut!(SCRATCH) }
+}
+
+/// The address of the scratch region. Guest pointers are 32 bits wide on
+/// wasm. Native builds have no guest memory to point into, so there it is 0.
+pub fn base() -> u32 {
+    if cfg!(target_family = "wasm") {
+        scratch().as_ptr() as usize as u32
+    } else {
+        0
+    }
+}
+
+/// Zeroes the scratch region, which in native builds outlives invocations.
+pub fn reset() {
+    scratch().fill(0);
+}
+
+/// Writes as many of `bytes` as fit at an offset into the scratch region,
+/// wrapped to its length, and returns the address of that offset.
+pub fn seed(offset: u32, bytes: &Bytes) -> u32 {
+    let offset = offset as usize % SCRATCH_LEN;
+    let len = (bytes.len() as usize).min(SCRATCH_LEN - offset);
+    let region = &mut scratch()[offset..offset + len];
+    bytes.slice(..len as u32).copy_into_slice(region);
+    base() + offset as u32
+}
+
+/// Cuts a length, counted in units of `unit` bytes, that a host function is
+/// passed along with `pos` down to what fits in the scratch region past a
+/// `Scratch` pointer, so that however much the host function writes stays
+/// inside the region. A `Wild` pointer keeps its length, so running past the
+/// region is left to those.
+pub fn fit(pos: &FuzzPointer, len: u32, unit: u32) -> u32 {
+    match pos {
+        FuzzPointer::Scratch(offset, _) => fit_at(*offset, len, unit),
+        FuzzPointer::Wild(_) => len,
+    }
+}
+
+/// `fit` for a `Scratch` pointer at `offset`, for the harness, whose
+/// `FuzzPointer` is its own type when it runs the wasm build.
+pub fn fit_at(offset: u32, len: u32, unit: u32) -> u32 {
+    let room = (SCRATCH_LEN - offset as usize % SCRATCH_LEN) as u32;
+    len.min(room / unit)
+}
+
+/// What a host function that writes to guest memory did to the scratch
+/// region: its address, and its contents before and after the call.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ScratchWrite {
+    pub base: u32,
+    pub before: Bytes,
+    pub after: Bytes,
+}
+
+/// The host functions that call into another contract.
+#[cfg(not(target_family = "wasm"))]
+const CALL_FUNCTIONS: &[&str] = &["call", "try_call"];
+
+/// Calls a host function, given by name. If it writes to guest memory, the
+/// result is a `ScratchWrite` rather than what the host function returned.
+pub fn watch(env: &Env, fn_name: &str, call: impl FnOnce() -> Val) -> Val {
+    // Each wasm instance has its own scratch region, but in native builds
+    // every copy of the contract shares this one, and the copy called into
+    // resets it. The caller's is put back afterwards, as it would be left
+    // alone under wasm.
+    #[cfg(not(target_family = "wasm"))]
+    if CALL_FUNCTIONS.contains(&fn_name) {
+        let saved = *scratch();
+        let v = call();
+        *scratch() = saved;
+        return v;
+    }
+
+    if !COPY_OUT_FUNCTIONS.contains(&fn_name) {
+        return call();
+    }
+
+    let before = Bytes::from_slice(env, scratch());
+    call();
+
+    ScratchWrite {
+        base: base(),
+        before,
+        after: Bytes::from_slice(env, scratch()),
+    }
+    .into_val(env)
+}
@@ -1,931 +1,510 @@
-use crate::{syscalls, FakeVal};
+use crate::{
+    scratch, syscalls, FuzzOperand, FuzzPointer, FuzzStorageType, Registers, SyscallResult,
+};
+use paste::paste;
 use soroban_env_common::{
-    BytesObject, DurationObject, Error, I128Object, I256Object, I256Val, I64Object, StorageType,
-    SymbolObject, TimepointObject, U128Object, U256Object, U256Val, U32Val, U64Object,
+    call_macro_with_all_host_functions, AddressObject, BytesObject, MapObject, StorageType,
+    StringObject, SymbolObject, U32Val, VecObject,
 };
-use soroban_sdk::contracttype;
-use soroban_sdk::{Address, Bytes, Env, Map, String, Symbol, TryFromVal, Val, Vec};
-
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum TypedFuzzInstruction {
-    Address(TypedModAddress),
-    Buf(TypedModBuf),
-    Call(TypedModCall),
-    Context(TypedModContext),
-    Crypto(TypedModCrypto),
-    Int(TypedModInt),
-    Ledger(TypedModLedger),
-    Map(TypedModMap),
-    Prng(TypedModPrng),
-    Test,
-    Vec(TypedModVec),
-}
+use soroban_sdk::{contracttype, Address, Bytes, Env, Map, String, Symbol, TryFromVal, Val, Vec};
 
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum TypedModAddress {
-    AccountPublicKeyToAddress(Bytes),
-    AddressToAccountPublicKey(Address),
-    AddressToContractId(Address),
-    AuthorizeAsCurrContract(Vec<Val>),
-    ContractIdToAddress(Bytes),
-    RequireAuth(Address),
-    RequireAuthForArgs(Address, Vec<Val>),
-}
+// Produces the type of an object argument of the typed instructions: either
+// an object, or the result of an earlier instruction in the same
+// `FuzzProgram`, which must be an object of the type the host expects.
+macro_rules! typed_object_operand {
+    ($name:ident($($sdk_type:tt)+) -> $object:ident) => {
+        #[contracttype]
+        #[derive(Clone, Debug)]
+        pub enum $name {
+            Val($($sdk_type)+),
+            Reg(u32),
+        }
 
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum TypedModBuf {
-    BytesAppend(Bytes, Bytes),
-    BytesBack(Bytes),
-    BytesCopyFromLinearMemory(Bytes, u32, u32, u32),
-    BytesCopyToLinearMemory(Bytes, u32, u32, u32),
-    BytesDel(Bytes, u32),
-    BytesFront(Bytes),
-    BytesGet(Bytes, u32),
-    BytesInsert(Bytes, u32, u32),
-    BytesLen(Bytes),
-    BytesNew,
-    BytesNewFromLinearMemory(u32, u32),
-    BytesPop(Bytes),
-    BytesPush(Bytes, u32),
-    BytesPut(Bytes, u32, u32),
-    BytesSlice(Bytes, u32, u32),
-    DeserializeFromBytes(Bytes),
-    SerializeToBytes(FakeVal),
-    StringCopyToLinearMemory(String, u32, u32, u32),
-    StringLen(String),
-    StringNewFromLinearMemory(u32, u32),
-    SymbolCopyToLinearMemory(Symbol, u32, u32, u32),
-    SymbolIndexInLinearMemory(Symbol, u32, u32),
-    SymbolLen(Symbol),
-    SymbolNewFromLinearMemory(u32, u32),
+        impl $name {
+            /// Resolves the operand to an object, failing if a register holds
+            /// anything else.
+            pub fn resolve(self, regs: &Registers) -> $object {
+                let val = match self {
+                    $name::Val(v) => v.to_val(),
+                    $name::Reg(i) => regs.get(FuzzOperand::Reg(i)).to_val(),
+                };
+                $object::try_from(val).unwrap()
+            }
+        }
+    };
 }
 
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum TypedModCall {
-    Call(Address, Symbol, Vec<Val>),
-    TryCall(Address, Symbol, Vec<Val>),
-}
+typed_object_operand!(AddressOperand(Address) -> AddressObject);
+typed_object_operand!(BytesOperand(Bytes) -> BytesObject);
+typed_object_operand!(MapOperand(Map<Val, Val>) -> MapObject);
+typed_object_operand!(StringOperand(String) -> StringObject);
+typed_object_operand!(VecOperand(Vec<Val>) -> VecObject);
 
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum TypedModContext {
-    ContractEvent(Vec<Val>, FakeVal),
-    FailWithError(FakeVal),
-    GetCurrentCallStack,
-    GetCurrentContractAddress,
-    GetInvokingContract,
-    GetLedgerNetworkId,
-    GetLedgerSequence,
-    GetLedgerTimestamp,
-    GetLedgerVersion,
-    GetMaxExpirationLedger,
-    LogFromLinearMemory(u32, u32, u32, u32),
-    ObjCmp(FakeVal, FakeVal),
-}
+// This is a callback macro that pattern-matches the token-tree passed by the
+// x-macro (call_macro_with_all_host_functions) and produces
+// `TypedFuzzInstruction`, with one variant per host module, along with one
+// `TypedModX` enum per module, with one variant per host function.
+//
+// Variant names are the camel-cased mod and fn names, shortened where needed
+// (see below), so the fuzz crate's prototypes, generated from the same
+// x-macro, line up with these enums.
+macro_rules! generate_typed_instructions {
+    {
+        $(
+            $(#[$mod_attr:meta])*
+            mod $mod_id:ident $mod_str:literal
+            {
+                $(
+                    $(#[$fn_attr:meta])*
+                    { $fn_str:literal, fn $fn_id:ident $args:tt -> $ret:ty }
+                )*
+            }
+        )*
+    }
 
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum TypedModCrypto {
-    ComputeHashKeccak256(Bytes),
-    ComputeHashSha256(Bytes),
-    RecoverKeyEcdsaSecp256k1(Bytes, Bytes, u32),
-    VerifySigEd25519(Bytes, Bytes, Bytes),
-}
+    =>
 
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum TypedModInt {
-    DurationObjFromU64(u64),
-    DurationObjToU64(FakeVal),
-    I256Add(FakeVal, FakeVal),
-    I256Div(FakeVal, FakeVal),
-    I256Mul(FakeVal, FakeVal),
-    I256Pow(FakeVal, u32),
-    I256Shl(FakeVal, u32),
-    I256Shr(FakeVal, u32),
-    I256Sub(FakeVal, FakeVal),
-    I256ObjFromBeBytes(Bytes),
-    I256ObjToBeBytes(FakeVal),
-    ObjFromI64(i64),
-    ObjFromI128Pieces(i64, u64),
-    ObjFromI256Pieces(i64, u64, u64, u64),
-    ObjFromU64(u64),
-    ObjFromU128Pieces(u64, u64),
-    ObjFromU256Pieces(u64, u64, u64, u64),
-    ObjToI64(i64),
-    ObjToI128Hi64(i128),
-    ObjToI128Lo64(i128),
-    ObjToI256HiHi(FakeVal),
-    ObjToI256HiLo(FakeVal),
-    ObjToI256LoHi(FakeVal),
-    ObjToI256LoLo(FakeVal),
-    ObjToU64(u64),
-    ObjToU128Hi64(u128),
-    ObjToU128Lo64(u128),
-    ObjToU256HiHi(FakeVal),
-    ObjToU256HiLo(FakeVal),
-    ObjToU256LoHi(FakeVal),
-    ObjToU256LoLo(FakeVal),
-    TimepointObjFromU64(u64),
-    TimepointObjToU64(FakeVal),
-    U256Add(FakeVal, FakeVal),
-    U256Div(FakeVal, FakeVal),
-    U256Mul(FakeVal, FakeVal),
-    U256Pow(FakeVal, u32),
-    U256Shl(FakeVal, u32),
-    U256Shr(FakeVal, u32),
-    U256Sub(FakeVal, FakeVal),
-    U256ValFromBeBytes(Bytes),
-    U256ValToBeBytes(FakeVal),
-}
+    {
+        paste! {
+            #[contracttype]
+            #[derive(Clone, Debug)]
+            pub enum TypedFuzzInstruction {
+                $(
+                    [<$mod_id:camel>]([<TypedMod $mod_id:camel>]),
+                )*
+            }
 
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum TypedModLedger {
-    BumpContractData(FakeVal, u32, u32),
-    BumpContractInstanceAndCode(Address, u32, u32),
-    BumpCurrentContract(u32, u32), // BumpCurrentContractInstanceAndCode
-    CreateAssetContract(Bytes),
-    CreateContract(Address, Bytes, Bytes),
-    DelContractData(FakeVal),
-    GetAssetContractId(Bytes),
-    GetContractData(FakeVal),
-    GetContractId(Address, Bytes),
-    HasContractData(FakeVal),
-    PutContractData(FakeVal, FakeVal),
-    UpdateCurrentContractWasm(Bytes),
-    UploadWasm(Bytes),
-}
+            impl TypedFuzzInstruction {
+                pub fn run(self, env: &Env, regs: &Registers) -> Val {
+                    match self {
+                        $(
+                            TypedFuzzInstruction::[<$mod_id:camel>](v) => v.run(env, regs),
+                        )*
+                    }
+                }
+            }
+        }
 
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum TypedModMap {
-    MapDel(Map<Val, Val>, FakeVal),
-    MapGet(Map<Val, Val>, FakeVal),
-    MapHas(Map<Val, Val>, FakeVal),
-    MapKeyByPos(Map<Val, Val>, u32),
-    MapKeys(Map<Val, Val>),
-    MapLen(Map<Val, Val>),
-    MapNew,
-    MapNewFromLinearMemory(u32, u32, u32),
-    MapPut(Map<Val, Val>, FakeVal, FakeVal),
-    MapUnpackToLinearMemory(Map<Val, Val>, u32, u32, u32),
-    MapValByPos(Map<Val, Val>, u32),
-    MapValues(Map<Val, Val>),
+        $(
+            // The `env` and `regs` identifiers are passed down so that the
+            // arms built by the helper macros below refer to the parameters
+            // of the `run` method they end up in.
+            generate_typed_module! { env regs $mod_id [] [] $($fn_id $args)* }
+        )*
+    };
 }
 
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum TypedModPrng {
-    PrngBytesNew(u32),
-    PrngReseed(Bytes),
-    PrngU64InInclusiveRange(u64, u64),
-    PrngVecShuffle(Vec<Val>),
-}
+// This is a helper macro used only by generate_typed_instructions above. It
+// consumes the functions of one module one at a time, accumulating the
+// variants of the module's enum and the arms of its `run` method, and emits
+// both once every function has been consumed.
+macro_rules! generate_typed_module {
+    {
+        $env:ident $regs:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*]
+    }
+    =>
+    {
+        paste! {
+            #[contracttype]
+            #[derive(Clone, Debug)]
+            pub enum [<TypedMod $mod_id:camel>] {
+                $($variant)*
+            }
 
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum TypedModVec {
-    VecAppend(Vec<Val>, Vec<Val>),
-    VecBack(Vec<Val>),
-    VecBinarySearch(Vec<Val>, FakeVal),
-    VecDel(Vec<Val>, u32),
-    VecFirstIndexOf(Vec<Val>, FakeVal),
-    VecFront(Vec<Val>),
-    VecGet(Vec<Val>, u32),
-    VecInsert(Vec<Val>, u32, FakeVal),
-    VecLastIndexOf(Vec<Val>, FakeVal),
-    VecLen(Vec<Val>),
-    VecNew,
-    VecNewFromLinearMemory(u32, u32),
-    VecPopBack(Vec<Val>),
-    VecPopFront(Vec<Val>),
-    VecPushBack(Vec<Val>, FakeVal),
-    VecPushFront(Vec<Val>, FakeVal),
-    VecPut(Vec<Val>, u32, FakeVal),
-    VecSlice(Vec<Val>, u32, u32),
-    VecUnpackToLinearMemory(Vec<Val>, u32, u32),
+            impl [<TypedMod $mod_id:camel>] {
+                // Some modules have no arguments that may name a register.
+                #[allow(unused_variables)]
+                pub fn run(self, $env: &Env, $regs: &Registers) -> Val {
+                    match self {
+                        $($arm)*
+                    }
+                }
+            }
+        }
+    };
+
+    // Contract specs limit variant names to 32 characters, so the few
+    // functions with longer names are given shorter ones here.
+    {
+        $env:ident $regs:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*]
+        bump_current_contract_instance_and_code ($($args:tt)*) $($rest:tt)*
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs
+            ($mod_id [$($variant)*] [$($arm)*] bump_current_contract_instance_and_code BumpCurrentContract [$($rest)*])
+            [] [] [] $($args)*
+        }
+    };
+
+    {
+        $env:ident $regs:ident $mod_id:ident [$($variant:tt)*] [$($arm:tt)*]
+        $fn_id:ident ($($args:tt)*) $($rest:tt)*
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs ($mod_id [$($variant)*] [$($arm)*] $fn_id [<$fn_id:camel>] [$($rest)*]) [] [] [] $($args)*
+        }
+    };
 }
 
-impl TypedFuzzInstruction {
-    pub fn run(self, env: &Env) {
-        let fuzz_instruction = self;
-        use TypedFuzzInstruction::*;
-        match fuzz_instruction {
-            Address(v) => match v {
-                TypedModAddress::AccountPublicKeyToAddress(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::address::account_public_key_to_address(v);
-                },
-                TypedModAddress::AddressToAccountPublicKey(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::address::address_to_account_public_key(v);
-                },
-                TypedModAddress::AddressToContractId(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::address::address_to_contract_id(v);
-                },
-                TypedModAddress::AuthorizeAsCurrContract(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::address::authorize_as_curr_contract(v);
-                },
-                TypedModAddress::ContractIdToAddress(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::address::contract_id_to_address(v);
-                },
-                TypedModAddress::RequireAuth(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::address::require_auth(v);
-                },
-                TypedModAddress::RequireAuthForArgs(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = v_1.to_object();
-                    syscalls::address::require_auth_for_args(v_0, v_1);
-                },
-            },
-            Buf(v) => match v {
-                TypedModBuf::BytesAppend(v_0, v_1) => unsafe {
-                    let v_0 = BytesObject::from(v_0);
-                    let v_1 = BytesObject::from(v_1);
-                    syscalls::buf::bytes_append(v_0, v_1);
-                },
-                TypedModBuf::BytesBack(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::buf::bytes_back(v);
-                },
-                TypedModBuf::BytesCopyFromLinearMemory(v_0, v_1, v_2, v_3) => unsafe {
-                    let v_0 = BytesObject::from(v_0);
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    let v_3 = U32Val::from(v_3);
+// This is a helper macro used only by generate_typed_module above. It
+// consumes the argument list of one function one argument at a time,
+// accumulating the field types of the function's variant, the names its
+// `run` arm binds, and the expressions passed to the syscall.
+//
+// Each argument is fuzzed as the SDK type the host expects for it, so the
+// host gets past its argument checks. Arguments with no natural SDK type,
+// including plain `Val`s, are `FuzzOperand`s. Those and object arguments may
+// also name a register, to pass the host an object an earlier instruction
+// made.
+macro_rules! generate_typed_args {
+    {
+        $env:ident $regs:ident ($mod_id:ident [$($variant:tt)*] [$($arm:tt)*] $fn_id:ident $name:tt [$($rest:tt)*])
+        [] [] [$($expr:tt)*]
+    }
+    =>
+    {
+        generate_typed_module! {
+            $env $regs $mod_id
+            [
+                $($variant)*
+                $name,
+            ]
+            [
+                $($arm)*
+                Self::$name => unsafe {
+                    syscalls::$mod_id::$fn_id($env, $($expr)*).into_result($env)
+                },
+            ]
+            $($rest)*
+        }
+    };
 
-                    syscalls::buf::bytes_copy_from_linear_memory(v_0, v_1, v_2, v_3);
-                },
-                TypedModBuf::BytesCopyToLinearMemory(v_0, v_1, v_2, v_3) => unsafe {
-                    let v_0 = BytesObject::from(v_0);
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    let v_3 = U32Val::from(v_3);
+    {
+        $env:ident $regs:ident ($mod_id:ident [$($variant:tt)*] [$($arm:tt)*] $fn_id:ident $name:tt [$($rest:tt)*])
+        [$($field:tt)+] [$($bind:tt)+] [$($expr:tt)*]
+    }
+    =>
+    {
+        generate_typed_module! {
+            $env $regs $mod_id
+            [
+                $($variant)*
+                $name($($field)+),
+            ]
+            [
+                $($arm)*
+                Self::$name($($bind)+) => unsafe {
+                    // The arguments are converted before the call, since
+                    // converting a `FuzzPointer` writes to the scratch region.
+                    let ($($bind)+) = ($($expr)*);
+                    scratch::watch($env, stringify!($fn_id), || {
+                        syscalls::$mod_id::$fn_id($env, $($bind)+).into_result($env)
+                    })
+                },
+            ]
+            $($rest)*
+        }
+    };
 
-                    syscalls::buf::bytes_copy_to_linear_memory(v_0, v_1, v_2, v_3);
-                },
-                TypedModBuf::BytesDel(v_0, v_1) => unsafe {
-                    let v_0 = BytesObject::from(v_0);
-                    let v_1 = U32Val::from(v_1);
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : StorageType $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func
+            [$($field)* FuzzStorageType,]
+            [$($bind)* $arg,]
+            [$($expr)* StorageType::from($arg),]
+            $($($args)*)?
+        }
+    };
 
-                    syscalls::buf::bytes_del(v_0, v_1);
-                },
-                TypedModBuf::BytesFront(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::buf::bytes_front(v);
-                },
-                TypedModBuf::BytesGet(v_0, v_1) => unsafe {
-                    let v_0 = BytesObject::from(v_0);
-                    let v_1 = U32Val::from(v_1);
+    // Arguments that point into guest linear memory, which the host
+    // functions name thus, are fuzzed as pointers into the scratch region
+    // rather than as plain integers. These arms mark them for the next one.
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        lm_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)*] [$($bind)*] [$($expr)*] lm_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
 
-                    syscalls::buf::bytes_get(v_0, v_1);
-                },
-                TypedModBuf::BytesInsert(v_0, v_1, v_2) => unsafe {
-                    let v_0 = BytesObject::from(v_0);
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        msg_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)*] [$($bind)*] [$($expr)*] msg_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
 
-                    syscalls::buf::bytes_insert(v_0, v_1, v_2);
-                },
-                TypedModBuf::BytesLen(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::buf::bytes_len(v);
-                },
-                TypedModBuf::BytesNew => unsafe {
-                    syscalls::buf::bytes_new();
-                },
-                TypedModBuf::BytesNewFromLinearMemory(v_0, v_1) => unsafe {
-                    let v_0 = U32Val::from(v_0);
-                    let v_1 = U32Val::from(v_1);
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        vals_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)*] [$($bind)*] [$($expr)*] vals_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
 
-                    syscalls::buf::bytes_new_from_linear_memory(v_0, v_1);
-                },
-                TypedModBuf::BytesPop(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::buf::bytes_pop(v);
-                },
-                TypedModBuf::BytesPush(v_0, v_1) => unsafe {
-                    let v_0 = BytesObject::from(v_0);
-                    let v_1 = U32Val::from(v_1);
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        keys_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)*] [$($bind)*] [$($expr)*] keys_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
 
-                    syscalls::buf::bytes_push(v_0, v_1);
-                },
-                TypedModBuf::BytesPut(v_0, v_1, v_2) => unsafe {
-                    let v_0 = BytesObject::from(v_0);
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        slices_pos : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)*] [$($bind)*] [$($expr)*] slices_pos : LinearMemoryPos $(, $($args)*)?
+        }
+    };
 
-                    syscalls::buf::bytes_put(v_0, v_1, v_2);
-                },
-                TypedModBuf::BytesSlice(v_0, v_1, v_2) => unsafe {
-                    let v_0 = BytesObject::from(v_0);
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
+    // The length after a pointer the host functions write through, or read
+    // from, is cut down to what fits in the scratch region past a `Scratch`
+    // pointer. Lengths passed with `vals_pos` count `Val`s.
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        lm_pos : LinearMemoryPos, len : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func
+            [$($field)* FuzzPointer, u32,]
+            [$($bind)* lm_pos, len,]
+            [$($expr)* lm_pos.to_pos(), U32Val::from(scratch::fit(&lm_pos, len, 1)),]
+            $($($args)*)?
+        }
+    };
 
-                    syscalls::buf::bytes_slice(v_0, v_1, v_2);
-                },
-                TypedModBuf::DeserializeFromBytes(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::buf::deserialize_from_bytes(v);
-                },
-                TypedModBuf::SerializeToBytes(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    syscalls::buf::serialize_to_bytes(v);
-                },
-                TypedModBuf::StringCopyToLinearMemory(v_0, v_1, v_2, v_3) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    let v_3 = U32Val::from(v_3);
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        vals_pos : LinearMemoryPos, len : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func
+            [$($field)* FuzzPointer, u32,]
+            [$($bind)* vals_pos, len,]
+            [$($expr)* vals_pos.to_pos(), U32Val::from(scratch::fit(&vals_pos, len, scratch::VAL_LEN)),]
+            $($($args)*)?
+        }
+    };
 
-                    syscalls::buf::string_copy_to_linear_memory(v_0, v_1, v_2, v_3);
-                },
-                TypedModBuf::StringLen(v_0) => unsafe {
-                    let v_0 = v_0.to_object();
-                    syscalls::buf::string_len(v_0);
-                },
-                TypedModBuf::StringNewFromLinearMemory(v_0, v_1) => unsafe {
-                    let v_0 = U32Val::from(v_0);
-                    let v_1 = U32Val::from(v_1);
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : LinearMemoryPos $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func
+            [$($field)* FuzzPointer,]
+            [$($bind)* $arg,]
+            [$($expr)* $arg.to_pos(),]
+            $($($args)*)?
+        }
+    };
 
-                    syscalls::buf::string_new_from_linear_memory(v_0, v_1);
-                },
-                TypedModBuf::SymbolCopyToLinearMemory(v_0, v_1, v_2, v_3) => unsafe {
-                    let v_0 = v_0.to_val();
-                    let v_0 = SymbolObject::try_from(v_0).unwrap();
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    let v_3 = U32Val::from(v_3);
-
-                    syscalls::buf::symbol_copy_to_linear_memory(v_0, v_1, v_2, v_3);
-                },
-                TypedModBuf::SymbolIndexInLinearMemory(v_0, v_1, v_2) => unsafe {
-                    let v_0 = v_0.to_symbol_val();
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    syscalls::buf::symbol_index_in_linear_memory(v_0, v_1, v_2);
-                },
-                TypedModBuf::SymbolLen(v) => unsafe {
-                    let v = v.to_val();
-                    let v = SymbolObject::try_from(v).unwrap();
-                    syscalls::buf::symbol_len(v);
-                },
-                TypedModBuf::SymbolNewFromLinearMemory(v_0, v_1) => unsafe {
-                    let v_0 = U32Val::from(v_0);
-                    let v_1 = U32Val::from(v_1);
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : U32Val $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* u32,] [$($bind)* $arg,] [$($expr)* U32Val::from($arg),] $($($args)*)?
+        }
+    };
 
-                    syscalls::buf::symbol_new_from_linear_memory(v_0, v_1);
-                },
-            },
-            Call(v) => match v {
-                TypedModCall::Call(v_0, v_1, v_2) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = v_1.to_symbol_val();
-                    let v_2 = v_2.to_object();
-                    syscalls::call::call(v_0, v_1, v_2);
-                },
-                TypedModCall::TryCall(v_0, v_1, v_2) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = v_1.to_symbol_val();
-                    let v_2 = v_2.to_object();
-                    syscalls::call::try_call(v_0, v_1, v_2);
-                },
-            },
-            Context(v) => match v {
-                TypedModContext::ContractEvent(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    syscalls::context::contract_event(v_0, v_1);
-                },
-                TypedModContext::FailWithError(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = Error::try_from(&v).unwrap();
-                    syscalls::context::fail_with_error(v);
-                },
-                TypedModContext::GetCurrentCallStack => unsafe {
-                    syscalls::context::get_current_call_stack();
-                },
-                TypedModContext::GetCurrentContractAddress => unsafe {
-                    syscalls::context::get_current_contract_address();
-                },
-                TypedModContext::GetInvokingContract => unsafe {
-                    syscalls::context::get_invoking_contract();
-                },
-                TypedModContext::GetLedgerNetworkId => unsafe {
-                    syscalls::context::get_ledger_network_id();
-                },
-                TypedModContext::GetLedgerSequence => unsafe {
-                    syscalls::context::get_ledger_sequence();
-                },
-                TypedModContext::GetLedgerTimestamp => unsafe {
-                    syscalls::context::get_ledger_timestamp();
-                },
-                TypedModContext::GetLedgerVersion => unsafe {
-                    syscalls::context::get_ledger_version();
-                },
-                TypedModContext::GetMaxExpirationLedger => unsafe {
-                    syscalls::context::get_max_expiration_ledger();
-                },
-                TypedModContext::LogFromLinearMemory(v_0, v_1, v_2, v_3) => unsafe {
-                    let v_0 = U32Val::from(v_0);
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    let v_3 = U32Val::from(v_3);
-                    syscalls::context::log_from_linear_memory(v_0, v_1, v_2, v_3);
-                },
-                TypedModContext::ObjCmp(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    syscalls::context::obj_cmp(v_0, v_1);
-                },
-            },
-            Crypto(v) => match v {
-                TypedModCrypto::ComputeHashKeccak256(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::crypto::compute_hash_keccak256(v);
-                },
-                TypedModCrypto::ComputeHashSha256(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::crypto::compute_hash_sha256(v);
-                },
-                TypedModCrypto::RecoverKeyEcdsaSecp256k1(v_0, v_1, v_2) => unsafe {
-                    let v_0 = BytesObject::from(v_0);
-                    let v_1 = BytesObject::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    syscalls::crypto::recover_key_ecdsa_secp256k1(v_0, v_1, v_2);
-                },
-                TypedModCrypto::VerifySigEd25519(v_0, v_1, v_2) => unsafe {
-                    let v_0 = BytesObject::from(v_0);
-                    let v_1 = BytesObject::from(v_1);
-                    let v_2 = BytesObject::from(v_2);
-                    syscalls::crypto::verify_sig_ed25519(v_0, v_1, v_2);
-                },
-            },
-            Int(v) => match v {
-                TypedModInt::DurationObjFromU64(v) => unsafe {
-                    syscalls::int::duration_obj_from_u64(v);
-                },
-                TypedModInt::DurationObjToU64(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = DurationObject::try_from(&v).unwrap();
-                    syscalls::int::duration_obj_to_u64(v);
-                },
-                TypedModInt::I256Add(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = I256Val::try_from(&v_0).unwrap();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    let v_1 = I256Val::try_from(&v_1).unwrap();
-                    syscalls::int::i256_add(v_0, v_1);
-                },
-                TypedModInt::I256Div(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = I256Val::try_from(&v_0).unwrap();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    let v_1 = I256Val::try_from(&v_1).unwrap();
-                    syscalls::int::i256_div(v_0, v_1);
-                },
-                TypedModInt::I256Mul(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = I256Val::try_from(&v_0).unwrap();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    let v_1 = I256Val::try_from(&v_1).unwrap();
-                    syscalls::int::i256_mul(v_0, v_1);
-                },
-                TypedModInt::I256Pow(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = I256Val::try_from(&v_0).unwrap();
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::int::i256_pow(v_0, v_1);
-                },
-                TypedModInt::I256Shl(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = I256Val::try_from(&v_0).unwrap();
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::int::i256_shl(v_0, v_1);
-                },
-                TypedModInt::I256Shr(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = I256Val::try_from(&v_0).unwrap();
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::int::i256_shr(v_0, v_1);
-                },
-                TypedModInt::I256Sub(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = I256Val::try_from(&v_0).unwrap();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    let v_1 = I256Val::try_from(&v_1).unwrap();
-                    syscalls::int::i256_sub(v_0, v_1);
-                },
-                TypedModInt::I256ObjFromBeBytes(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::int::i256_val_from_be_bytes(v);
-                },
-                TypedModInt::I256ObjToBeBytes(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = I256Val::try_from(&v).unwrap();
-                    syscalls::int::i256_val_to_be_bytes(v);
-                },
-                TypedModInt::ObjFromI64(v) => unsafe {
-                    syscalls::int::obj_from_i64(v);
-                },
-                TypedModInt::ObjFromI128Pieces(v_0, v_1) => unsafe {
-                    syscalls::int::obj_from_i128_pieces(v_0, v_1);
-                },
-                TypedModInt::ObjFromI256Pieces(v_0, v_1, v_2, v_3) => unsafe {
-                    syscalls::int::obj_from_i256_pieces(v_0, v_1, v_2, v_3);
-                },
-                TypedModInt::ObjFromU64(v) => unsafe {
-                    syscalls::int::obj_from_u64(v);
-                },
-                TypedModInt::ObjFromU128Pieces(v_0, v_1) => unsafe {
-                    syscalls::int::obj_from_u128_pieces(v_0, v_1);
-                },
-                TypedModInt::ObjFromU256Pieces(v_0, v_1, v_2, v_3) => unsafe {
-                    syscalls::int::obj_from_u256_pieces(v_0, v_1, v_2, v_3);
-                },
-                TypedModInt::ObjToI64(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = I64Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_i64(v);
-                },
-                TypedModInt::ObjToI128Hi64(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = I128Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_i128_hi64(v);
-                },
-                TypedModInt::ObjToI128Lo64(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = I128Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_i128_lo64(v);
-                },
-                TypedModInt::ObjToI256HiHi(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = I256Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_i256_hi_hi(v);
-                },
-                TypedModInt::ObjToI256HiLo(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = I256Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_i256_hi_lo(v);
-                },
-                TypedModInt::ObjToI256LoHi(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = I256Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_i256_lo_hi(v);
-                },
-                TypedModInt::ObjToI256LoLo(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = I256Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_i256_lo_lo(v);
-                },
-                TypedModInt::ObjToU64(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = U64Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_u64(v);
-                },
-                TypedModInt::ObjToU128Hi64(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = U128Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_u128_hi64(v);
-                },
-                TypedModInt::ObjToU128Lo64(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = U128Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_u128_lo64(v);
-                },
-                TypedModInt::ObjToU256HiHi(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = U256Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_u256_hi_hi(v);
-                },
-                TypedModInt::ObjToU256HiLo(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = U256Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_u256_hi_lo(v);
-                },
-                TypedModInt::ObjToU256LoHi(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = U256Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_u256_lo_hi(v);
-                },
-                TypedModInt::ObjToU256LoLo(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = U256Object::try_from(&v).unwrap();
-                    syscalls::int::obj_to_u256_lo_lo(v);
-                },
-                TypedModInt::TimepointObjFromU64(v) => unsafe {
-                    syscalls::int::timepoint_obj_from_u64(v);
-                },
-                TypedModInt::TimepointObjToU64(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = TimepointObject::try_from(&v).unwrap();
-                    syscalls::int::timepoint_obj_to_u64(v);
-                },
-                TypedModInt::U256Add(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = U256Val::try_from(&v_0).unwrap();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    let v_1 = U256Val::try_from(&v_1).unwrap();
-                    syscalls::int::u256_add(v_0, v_1);
-                },
-                TypedModInt::U256Div(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = U256Val::try_from(&v_0).unwrap();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    let v_1 = U256Val::try_from(&v_1).unwrap();
-                    syscalls::int::u256_div(v_0, v_1);
-                },
-                TypedModInt::U256Mul(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = U256Val::try_from(&v_0).unwrap();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    let v_1 = U256Val::try_from(&v_1).unwrap();
-                    syscalls::int::u256_mul(v_0, v_1);
-                },
-                TypedModInt::U256Pow(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = U256Val::try_from(&v_0).unwrap();
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::int::u256_pow(v_0, v_1);
-                },
-                TypedModInt::U256Shl(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = U256Val::try_from(&v_0).unwrap();
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::int::u256_shl(v_0, v_1);
-                },
-                TypedModInt::U256Shr(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = U256Val::try_from(&v_0).unwrap();
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::int::u256_shr(v_0, v_1);
-                },
-                TypedModInt::U256Sub(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_0 = U256Val::try_from(&v_0).unwrap();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    let v_1 = U256Val::try_from(&v_1).unwrap();
-                    syscalls::int::u256_sub(v_0, v_1);
-                },
-                TypedModInt::U256ValFromBeBytes(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::int::u256_val_from_be_bytes(v);
-                },
-                TypedModInt::U256ValToBeBytes(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    let v = U256Val::try_from(&v).unwrap();
-                    syscalls::int::u256_val_to_be_bytes(v);
-                },
-            },
-            Ledger(v) => match v {
-                TypedModLedger::BumpContractData(v_0, v_1, v_2) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    syscalls::ledger::bump_contract_data(v_0, StorageType::Temporary, v_1, v_2);
-                },
-                TypedModLedger::BumpContractInstanceAndCode(v_0, v_1, v_2) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    syscalls::ledger::bump_contract_instance_and_code(v_0, v_1, v_2);
-                },
-                TypedModLedger::BumpCurrentContract(v_0, v_1) => unsafe {
-                    let v_0 = U32Val::from(v_0);
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::ledger::bump_current_contract_instance_and_code(v_0, v_1);
-                },
-                TypedModLedger::CreateAssetContract(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::ledger::create_asset_contract(v);
-                },
-                TypedModLedger::CreateContract(v_0, v_1, v_2) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = BytesObject::from(v_1);
-                    let v_2 = BytesObject::from(v_2);
-                    syscalls::ledger::create_contract(v_0, v_1, v_2);
-                },
-                TypedModLedger::DelContractData(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    syscalls::ledger::del_contract_data(v, StorageType::Temporary);
-                },
-                TypedModLedger::GetAssetContractId(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::ledger::get_asset_contract_id(v);
-                },
-                TypedModLedger::GetContractData(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    syscalls::ledger::get_contract_data(v, StorageType::Temporary);
-                },
-                TypedModLedger::GetContractId(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = BytesObject::from(v_1);
-                    syscalls::ledger::get_contract_id(v_0, v_1);
-                },
-                TypedModLedger::HasContractData(v) => unsafe {
-                    let v = Val::try_from_val(env, &v).unwrap();
-                    syscalls::ledger::has_contract_data(v, StorageType::Temporary);
-                },
-                TypedModLedger::PutContractData(v_0, v_1) => unsafe {
-                    let v_0 = Val::try_from_val(env, &v_0).unwrap();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    syscalls::ledger::put_contract_data(v_0, v_1, StorageType::Temporary);
-                },
-                TypedModLedger::UpdateCurrentContractWasm(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::ledger::update_current_contract_wasm(v);
-                },
-                TypedModLedger::UploadWasm(v) => unsafe {
-                    let v = BytesObject::from(v);
-                    syscalls::ledger::upload_wasm(v);
-                },
-            },
-            Map(v) => match v {
-                TypedModMap::MapDel(v_0, v_1) => unsafe {
-                    // todo: private method
-                    let v_0 = v_0.to_object();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    syscalls::map::map_del(v_0, v_1);
-                },
-                TypedModMap::MapGet(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    syscalls::map::map_get(v_0, v_1);
-                },
-                TypedModMap::MapHas(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    syscalls::map::map_has(v_0, v_1);
-                },
-                TypedModMap::MapKeyByPos(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::map::map_key_by_pos(v_0, v_1);
-                },
-                TypedModMap::MapKeys(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::map::map_keys(v);
-                },
-                TypedModMap::MapLen(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::map::map_len(v);
-                },
-                TypedModMap::MapNew => unsafe {
-                    syscalls::map::map_new();
-                },
-                TypedModMap::MapNewFromLinearMemory(v_0, v_1, v_2) => unsafe {
-                    let v_0 = U32Val::from(v_0);
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    syscalls::map::map_new_from_linear_memory(v_0, v_1, v_2);
-                },
-                TypedModMap::MapPut(v_0, v_1, v_2) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    let v_2 = Val::try_from_val(env, &v_2).unwrap();
-                    syscalls::map::map_put(v_0, v_1, v_2);
-                },
-                TypedModMap::MapUnpackToLinearMemory(v_0, v_1, v_2, v_3) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    let v_3 = U32Val::from(v_3);
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : u64 $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* u64,] [$($bind)* $arg,] [$($expr)* $arg,] $($($args)*)?
+        }
+    };
 
-                    syscalls::map::map_unpack_to_linear_memory(v_0, v_1, v_2, v_3);
-                },
-                TypedModMap::MapValByPos(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::map::map_val_by_pos(v_0, v_1);
-                },
-                TypedModMap::MapValues(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::map::map_values(v);
-                },
-            },
-            Prng(v) => match v {
-                TypedModPrng::PrngBytesNew(v) => unsafe {
-                    let v = U32Val::from(v);
-                    syscalls::prng::prng_bytes_new(v);
-                },
-                TypedModPrng::PrngReseed(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::prng::prng_reseed(v);
-                },
-                TypedModPrng::PrngU64InInclusiveRange(v_0, v_1) => unsafe {
-                    syscalls::prng::prng_u64_in_inclusive_range(v_0, v_1);
-                },
-                TypedModPrng::PrngVecShuffle(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::prng::prng_vec_shuffle(v);
-                },
-            },
-            Test => unsafe {
-                syscalls::test::dummy0();
-            },
-            Vec(v) => match v {
-                TypedModVec::VecAppend(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = v_1.to_object();
-                    syscalls::vec::vec_append(v_0, v_1);
-                },
-                TypedModVec::VecBack(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::vec::vec_back(v);
-                },
-                TypedModVec::VecBinarySearch(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    syscalls::vec::vec_binary_search(v_0, v_1);
-                },
-                TypedModVec::VecDel(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::vec::vec_del(v_0, v_1);
-                },
-                TypedModVec::VecFirstIndexOf(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    syscalls::vec::vec_first_index_of(v_0, v_1);
-                },
-                TypedModVec::VecFront(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::vec::vec_front(v);
-                },
-                TypedModVec::VecGet(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::vec::vec_get(v_0, v_1);
-                },
-                TypedModVec::VecInsert(v_0, v_1, v_2) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = Val::try_from_val(env, &v_2).unwrap();
-                    syscalls::vec::vec_insert(v_0, v_1, v_2);
-                },
-                TypedModVec::VecLastIndexOf(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    syscalls::vec::vec_last_index_of(v_0, v_1);
-                },
-                TypedModVec::VecLen(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::vec::vec_len(v);
-                },
-                TypedModVec::VecNew => unsafe {
-                    syscalls::vec::vec_new();
-                },
-                TypedModVec::VecNewFromLinearMemory(v_0, v_1) => unsafe {
-                    let v_0 = U32Val::from(v_0);
-                    let v_1 = U32Val::from(v_1);
-                    syscalls::vec::vec_new_from_linear_memory(v_0, v_1);
-                },
-                TypedModVec::VecPopBack(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::vec::vec_pop_back(v);
-                },
-                TypedModVec::VecPopFront(v) => unsafe {
-                    let v = v.to_object();
-                    syscalls::vec::vec_pop_front(v);
-                },
-                TypedModVec::VecPushBack(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    syscalls::vec::vec_push_back(v_0, v_1);
-                },
-                TypedModVec::VecPushFront(v_0, v_1) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = Val::try_from_val(env, &v_1).unwrap();
-                    syscalls::vec::vec_push_front(v_0, v_1);
-                },
-                TypedModVec::VecPut(v_0, v_1, v_2) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = Val::try_from_val(env, &v_2).unwrap();
-                    syscalls::vec::vec_put(v_0, v_1, v_2);
-                },
-                TypedModVec::VecSlice(v_0, v_1, v_2) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    syscalls::vec::vec_slice(v_0, v_1, v_2);
-                },
-                TypedModVec::VecUnpackToLinearMemory(v_0, v_1, v_2) => unsafe {
-                    let v_0 = v_0.to_object();
-                    let v_1 = U32Val::from(v_1);
-                    let v_2 = U32Val::from(v_2);
-                    syscalls::vec::vec_unpack_to_linear_memory(v_0, v_1, v_2);
-                },
-            },
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : i64 $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* i64,] [$($bind)* $arg,] [$($expr)* $arg,] $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : AddressObject $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* AddressOperand,] [$($bind)* $arg,] [$($expr)* $arg.resolve($regs),] $($($args)*)?
         }
+    };
+
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : BytesObject $(, $($args:tt)*)?
     }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* BytesOperand,] [$($bind)* $arg,] [$($expr)* $arg.resolve($regs),] $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : MapObject $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* MapOperand,] [$($bind)* $arg,] [$($expr)* $arg.resolve($regs),] $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : StringObject $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* StringOperand,] [$($bind)* $arg,] [$($expr)* $arg.resolve($regs),] $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : VecObject $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* VecOperand,] [$($bind)* $arg,] [$($expr)* $arg.resolve($regs),] $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : Symbol $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* Symbol,] [$($bind)* $arg,] [$($expr)* $arg.to_symbol_val(),] $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : SymbolObject $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func
+            [$($field)* Symbol,]
+            [$($bind)* $arg,]
+            [$($expr)* SymbolObject::try_from($arg.to_val()).unwrap(),]
+            $($($args)*)?
+        }
+    };
+
+    // Integer objects are fuzzed as the integers they hold.
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : I64Object $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* i64,] [$($bind)* $arg,] [$($expr)* Val::try_from_val($env, &$arg).unwrap().try_into().unwrap(),] $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : U64Object $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* u64,] [$($bind)* $arg,] [$($expr)* Val::try_from_val($env, &$arg).unwrap().try_into().unwrap(),] $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : I128Object $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* i128,] [$($bind)* $arg,] [$($expr)* Val::try_from_val($env, &$arg).unwrap().try_into().unwrap(),] $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : U128Object $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func [$($field)* u128,] [$($bind)* $arg,] [$($expr)* Val::try_from_val($env, &$arg).unwrap().try_into().unwrap(),] $($($args)*)?
+        }
+    };
+
+    {
+        $env:ident $regs:ident $func:tt [$($field:tt)*] [$($bind:tt)*] [$($expr:tt)*]
+        $arg:ident : $type:ident $(, $($args:tt)*)?
+    }
+    =>
+    {
+        generate_typed_args! {
+            $env $regs $func
+            [$($field)* FuzzOperand,]
+            [$($bind)* $arg,]
+            [$($expr)* $regs.get($arg).to_val().try_into().unwrap(),]
+            $($($args)*)?
+        }
+    };
 }
+
+// Here we invoke the x-macro passing generate_typed_instructions as its callback macro.
+call_macro_with_all_host_functions! { generate_typed_instructions }